@@ -1,10 +1,11 @@
 //! Benchmarks for the rust-random-logo library
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::SeedableRng;
 use rand_xoshiro::Xoshiro256PlusPlus;
 
-use rust_random_logo::{rand_sigma_factor_ifs, render, Config};
+use rust_random_logo::{normalize_points, rand_sigma_factor_ifs, render, Config, IFS};
 
 fn bench_generate_ifs(c: &mut Criterion) {
     c.bench_function("generate_ifs", |b| {
@@ -27,6 +28,7 @@ fn bench_render_small(c: &mut Criterion) {
         ndims: 2,
         rng_name: "Xoshiro256PlusPlus".to_string(),
         seed: 42,
+        ..Config::default()
     };
 
     c.bench_function("render_small", |b| {
@@ -49,6 +51,7 @@ fn bench_render_medium(c: &mut Criterion) {
         ndims: 2,
         rng_name: "Xoshiro256PlusPlus".to_string(),
         seed: 42,
+        ..Config::default()
     };
 
     c.bench_function("render_medium", |b| {
@@ -59,10 +62,60 @@ fn bench_render_medium(c: &mut Criterion) {
     });
 }
 
+fn bench_apply_random(c: &mut Criterion) {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let point = nalgebra::Vector2::new(0.1, 0.2);
+
+    let mut group = c.benchmark_group("apply_random");
+
+    // Sampler cached in the IFS at construction
+    group.bench_function("cached", |b| {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+        b.iter(|| black_box(ifs.apply_random(&mut rng, &point)))
+    });
+
+    // Sampler rebuilt at every step, as before it was cached
+    group.bench_function("rebuilt", |b| {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+        b.iter(|| {
+            let dist = WeightedIndex::new(ifs.weights()).unwrap();
+            let idx = dist.sample(&mut rng);
+            black_box(ifs.transforms()[idx].apply(&point))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_normalize(c: &mut Criterion) {
+    // A raw orbit of one million points
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let mut point = nalgebra::Vector2::zeros();
+    let (xs, ys): (Vec<f64>, Vec<f64>) = (0..1_000_000)
+        .map(|_| {
+            point = ifs.apply_random(&mut rng, &point);
+            (point.x, point.y)
+        })
+        .unzip();
+
+    c.bench_function("normalize", |b| {
+        b.iter(|| {
+            let mut xs = xs.clone();
+            let mut ys = ys.clone();
+            normalize_points(&mut xs, &mut ys, 384, 384);
+            black_box((xs, ys))
+        })
+    });
+}
+
 criterion_group!(
     benches,
     bench_generate_ifs,
     bench_render_small,
-    bench_render_medium
+    bench_render_medium,
+    bench_apply_random,
+    bench_normalize
 );
 criterion_main!(benches);
@@ -1,14 +1,12 @@
 //! Basic example of using the rust-random-logo library
 
-use rand::SeedableRng;
-use rand_xoshiro::Xoshiro256PlusPlus;
 use std::path::PathBuf;
 
-use rust_random_logo::{rand_sigma_factor_ifs, render, Config};
+use rust_random_logo::{rand_sigma_factor_ifs, render, seeded_rng, Config};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create a random number generator with a seed
-    let mut rng = Xoshiro256PlusPlus::seed_from_u64(99);
+    let mut rng = seeded_rng(99);
 
     // Create a random IFS
     let ifs = rand_sigma_factor_ifs(&mut rng);
@@ -22,6 +20,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ndims: 2,
         rng_name: "Xoshiro256PlusPlus".to_string(),
         seed: 99,
+        ..Config::default()
     };
 
     // Render the image
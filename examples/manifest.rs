@@ -0,0 +1,44 @@
+//! Dataset example of using the rust-random-logo library
+//!
+//! This example renders a few fractals and writes a JSON manifest next to
+//! each image, recording the seed, configuration and IFS that produced it.
+
+use std::fs;
+use std::path::PathBuf;
+
+use rust_random_logo::{generate_with_manifest, Config};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Configuration shared by every image of the dataset
+    let base_config = Config {
+        height: 256,
+        width: 256,
+        npoints: 50_000,
+        max_retries: 5,
+        ..Config::default()
+    };
+
+    let out_dir = PathBuf::from("dataset");
+    fs::create_dir_all(&out_dir)?;
+
+    for seed in 0..4 {
+        let config = Config {
+            seed,
+            ..base_config.clone()
+        };
+
+        // Render the image along with its manifest
+        println!("Rendering fractal with seed {}...", seed);
+        let (image, manifest) = generate_with_manifest(&config)?;
+
+        // Save both side by side
+        image.save(out_dir.join(format!("fractal_{}.png", seed)))?;
+        fs::write(
+            out_dir.join(format!("fractal_{}.json", seed)),
+            manifest.to_json()?,
+        )?;
+    }
+
+    println!("Saved dataset to {}", out_dir.display());
+    Ok(())
+}
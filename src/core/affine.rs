@@ -3,7 +3,8 @@
 //! This module provides the Affine transformation struct and related functions.
 //! An affine transformation is defined as f(x) = Wx + b, where W is a matrix and b is a vector.
 
-// No imports needed here
+use nalgebra::Matrix3;
+
 use crate::core::types::{Matrix2f, Vector2f};
 
 /// Affine transformation struct
@@ -33,6 +34,39 @@ impl Affine {
         Self { w, b }
     }
 
+    /// Create an affine transformation from its six coefficients
+    ///
+    /// The coefficients `[a, b, c, d, e, f]` describe the map
+    /// `(x, y) -> (a * x + b * y + e, c * x + d * y + f)`, that is
+    /// `W = [[a, b], [c, d]]` in row-major order and `b = (e, f)`. This is
+    /// the ordering of the IFS codes of Apophysis and FractInt.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficients` - The coefficients `[a, b, c, d, e, f]`
+    ///
+    /// # Returns
+    ///
+    /// A new Affine transformation
+    pub fn from_coefficients(coefficients: [f64; 6]) -> Self {
+        let [a, b, c, d, e, f] = coefficients;
+        Self::new(Matrix2f::new(a, b, c, d), Vector2f::new(e, f))
+    }
+
+    /// Get the six coefficients of the transformation
+    ///
+    /// This is the inverse of [`from_coefficients`](Self::from_coefficients).
+    ///
+    /// # Returns
+    ///
+    /// The coefficients `[a, b, c, d, e, f]` of the map
+    /// `(x, y) -> (a * x + b * y + e, c * x + d * y + f)`
+    pub fn as_coefficients(&self) -> [f64; 6] {
+        [
+            self.w.m11, self.w.m12, self.w.m21, self.w.m22, self.b.x, self.b.y,
+        ]
+    }
+
     /// Apply the affine transformation to a point
     ///
     /// # Arguments
@@ -46,6 +80,30 @@ impl Affine {
         self.w * point + self.b
     }
 
+    /// Apply the affine transformation to a slice of points
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points to transform
+    /// * `out` - Output slice receiving the transformed points
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `out` have different lengths
+    pub fn apply_many(&self, points: &[Vector2f], out: &mut [Vector2f]) {
+        assert_eq!(
+            points.len(),
+            out.len(),
+            "Output must have as many points as the input"
+        );
+
+        // Hoist the coefficients so the loop is a plain fused multiply-add
+        let [a, b, c, d, e, f] = self.as_coefficients();
+        for (p, o) in points.iter().zip(out.iter_mut()) {
+            *o = Vector2f::new(a * p.x + b * p.y + e, c * p.x + d * p.y + f);
+        }
+    }
+
     /// Get the determinant of the transformation matrix
     ///
     /// # Returns
@@ -54,6 +112,80 @@ impl Affine {
     pub fn determinant(&self) -> f64 {
         self.w.determinant()
     }
+
+    /// Get the contraction ratio of the transformation
+    ///
+    /// The contraction ratio is the largest singular value of the
+    /// transformation matrix, i.e. the Lipschitz constant of the map.
+    ///
+    /// # Returns
+    ///
+    /// The largest singular value of the transformation matrix
+    pub fn contraction_ratio(&self) -> f64 {
+        self.w.singular_values().max()
+    }
+
+    /// Check whether the transformation is a contraction
+    ///
+    /// # Returns
+    ///
+    /// `true` if the largest singular value is strictly less than 1
+    pub fn is_contractive(&self) -> bool {
+        self.contraction_ratio() < 1.0
+    }
+
+    /// Get the fixed point of the transformation
+    ///
+    /// The fixed point solves `Wx + b = x`, i.e. `x = (I - W)^-1 b`. For a
+    /// contraction it is unique and every orbit of the map converges to it.
+    ///
+    /// # Returns
+    ///
+    /// The fixed point, or `None` if `I - W` is singular (1 is an eigenvalue
+    /// of `W`), in which case the map has no fixed point or infinitely many
+    pub fn fixed_point(&self) -> Option<Vector2f> {
+        (Matrix2f::identity() - self.w)
+            .try_inverse()
+            .map(|m| m * self.b)
+            .filter(|p| p.iter().all(|v| v.is_finite()))
+    }
+
+    /// Convert the transformation to a 3x3 homogeneous matrix
+    ///
+    /// The result has the layout
+    ///
+    /// ```text
+    /// | w11 w12 b1 |
+    /// | w21 w22 b2 |
+    /// |  0   0   1 |
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The homogeneous matrix of the transformation
+    pub fn to_homogeneous(&self) -> Matrix3<f64> {
+        let mut m = self.w.to_homogeneous();
+        m[(0, 2)] = self.b.x;
+        m[(1, 2)] = self.b.y;
+        m
+    }
+
+    /// Create an affine transformation from a 3x3 homogeneous matrix
+    ///
+    /// The bottom row of the matrix is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The homogeneous matrix
+    ///
+    /// # Returns
+    ///
+    /// A new Affine transformation
+    pub fn from_homogeneous(m: &Matrix3<f64>) -> Self {
+        let w = m.fixed_view::<2, 2>(0, 0).into_owned();
+        let b = Vector2f::new(m[(0, 2)], m[(1, 2)]);
+        Self::new(w, b)
+    }
 }
 
 // Implement function-like behavior for Affine
@@ -0,0 +1,449 @@
+//! Analysis functions for the rust-random-logo library
+//!
+//! This module provides functions for measuring properties of generated
+//! point clouds and rendered images.
+
+use image::RgbImage;
+
+use crate::core::affine::Affine;
+use crate::core::ifs::SigmaFactorIFS;
+use crate::core::renderer::EMPTY_BOUNDS;
+
+/// Compute the convex hull of a point cloud
+///
+/// Uses Andrew's monotone chain algorithm, which runs in O(n log n).
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+///
+/// # Returns
+///
+/// The hull vertices in counterclockwise order, without repeating the first
+/// vertex. Collinear points on the hull edges are omitted, so a collinear
+/// cloud yields its two endpoints, and inputs with fewer than three distinct
+/// points yield those points. Points with a non-finite coordinate, as left
+/// by a diverged orbit, are ignored.
+pub fn convex_hull(xs: &[f64], ys: &[f64]) -> Vec<(f64, f64)> {
+    let mut points: Vec<(f64, f64)> = xs
+        .iter()
+        .copied()
+        .zip(ys.iter().copied())
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    // Cross product of (a - o) and (b - o)
+    let cross = |o: (f64, f64), a: (f64, f64), b: (f64, f64)| {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+
+    let mut hull: Vec<(f64, f64)> = Vec::with_capacity(2 * points.len());
+
+    // Lower hull
+    for &p in &points {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // Upper hull
+    let lower_len = hull.len() + 1;
+    for &p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // The last point is the first one again
+    hull.pop();
+
+    hull
+}
+
+/// Largest number of transformations matched optimally by [`ifs_distance`]
+const MAX_OPTIMAL_MATCHING: usize = 8;
+
+/// Compute a permutation-invariant distance between two IFS
+///
+/// The distance between two transformations is the Frobenius norm of the
+/// difference of their 2x3 `[w | b]` matrices. Transformations of `a` are
+/// matched one-to-one with transformations of `b` so that the sum of these
+/// distances is minimal, which makes the result independent of the order in
+/// which the transformations are stored. The matching is solved exactly by
+/// trying all permutations for up to 8 transformations, and greedily (closest
+/// pair first) beyond that.
+///
+/// # Arguments
+///
+/// * `a` - The first IFS
+/// * `b` - The second IFS
+///
+/// # Returns
+///
+/// The sum of distances between matched transformations, or `f64::INFINITY`
+/// if the IFS have a different number of transformations
+pub fn ifs_distance(a: &SigmaFactorIFS, b: &SigmaFactorIFS) -> f64 {
+    let n = a.transforms().len();
+    if n != b.transforms().len() {
+        return f64::INFINITY;
+    }
+
+    let distance = |ta: &Affine, tb: &Affine| {
+        ((ta.w - tb.w).norm_squared() + (ta.b - tb.b).norm_squared()).sqrt()
+    };
+    let costs: Vec<Vec<f64>> = a
+        .transforms()
+        .iter()
+        .map(|ta| b.transforms().iter().map(|tb| distance(ta, tb)).collect())
+        .collect();
+
+    if n <= MAX_OPTIMAL_MATCHING {
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut best = f64::INFINITY;
+        permute(&mut perm, 0, &costs, &mut best);
+        best
+    } else {
+        greedy_matching(&costs)
+    }
+}
+
+/// Find the cheapest assignment by enumerating permutations
+///
+/// # Arguments
+///
+/// * `perm` - The permutation being built; `perm[..k]` is fixed
+/// * `k` - Number of fixed entries
+/// * `costs` - `costs[i][j]` is the cost of matching `i` with `j`
+/// * `best` - The cheapest total cost found so far
+fn permute(perm: &mut [usize], k: usize, costs: &[Vec<f64>], best: &mut f64) {
+    if k == perm.len() {
+        let total: f64 = perm.iter().enumerate().map(|(i, &j)| costs[i][j]).sum();
+        *best = best.min(total);
+        return;
+    }
+
+    for i in k..perm.len() {
+        perm.swap(k, i);
+        permute(perm, k + 1, costs, best);
+        perm.swap(k, i);
+    }
+}
+
+/// Approximate the cheapest assignment by repeatedly matching the closest pair
+///
+/// # Arguments
+///
+/// * `costs` - `costs[i][j]` is the cost of matching `i` with `j`
+///
+/// # Returns
+///
+/// The total cost of the greedy assignment
+fn greedy_matching(costs: &[Vec<f64>]) -> f64 {
+    let n = costs.len();
+    let mut pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).collect();
+    pairs.sort_by(|&(i1, j1), &(i2, j2)| costs[i1][j1].total_cmp(&costs[i2][j2]));
+
+    let mut used_a = vec![false; n];
+    let mut used_b = vec![false; n];
+    let mut total = 0.0;
+    for (i, j) in pairs {
+        if !used_a[i] && !used_b[j] {
+            used_a[i] = true;
+            used_b[j] = true;
+            total += costs[i][j];
+        }
+    }
+
+    total
+}
+
+/// Compute the fraction of an image covered by the attractor
+///
+/// # Arguments
+///
+/// * `img` - The rendered image
+/// * `background` - The background color of the canvas
+///
+/// # Returns
+///
+/// The fraction in [0, 1] of pixels that differ from the background
+pub fn coverage(img: &RgbImage, background: [u8; 3]) -> f64 {
+    let total = img.width() as usize * img.height() as usize;
+    if total == 0 {
+        return 0.0;
+    }
+    let covered = img.pixels().filter(|p| p.0 != background).count();
+
+    covered as f64 / total as f64
+}
+
+/// Compute the histogram of the perceptual luminance of an image
+///
+/// The luminance of a pixel is `0.2126 R + 0.7152 G + 0.0722 B` (Rec. 709),
+/// rounded to the nearest integer.
+///
+/// # Arguments
+///
+/// * `img` - The image to analyze
+///
+/// # Returns
+///
+/// The number of pixels at each luminance level from 0 to 255
+pub fn luminance_histogram(img: &RgbImage) -> [u32; 256] {
+    let mut histogram = [0u32; 256];
+    for pixel in img.pixels() {
+        let [r, g, b] = pixel.0;
+        let luminance = 0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64;
+        histogram[luminance.round().min(255.0) as usize] += 1;
+    }
+
+    histogram
+}
+
+/// Number of bisection steps used by [`similarity_dimension`]
+const MORAN_ITERATIONS: usize = 100;
+
+/// Compute the similarity dimension of an IFS
+///
+/// The similarity dimension is the solution `s` of Moran's equation
+/// `sum(r_i^s) = 1`, where `r_i` are the contraction ratios of the
+/// transformations (their largest singular values), found by bisection.
+/// It equals the Hausdorff dimension of the attractor only for similitudes
+/// satisfying the open set condition; for general affine maps it is an
+/// upper bound based on the largest stretch of every map, and overlapping
+/// pieces make the true dimension smaller still.
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+///
+/// # Returns
+///
+/// The similarity dimension, or `f64::INFINITY` if some transformation is
+/// not a contraction so that Moran's equation has no solution
+pub fn similarity_dimension(ifs: &SigmaFactorIFS) -> f64 {
+    let ratios = ifs.contraction_ratios();
+    if ratios.iter().any(|&r| r.is_nan() || r >= 1.0) {
+        return f64::INFINITY;
+    }
+
+    // The sum decreases from the number of maps at s = 0 towards zero
+    let moran = |s: f64| ratios.iter().map(|&r| r.powf(s)).sum::<f64>() - 1.0;
+    if moran(0.0) <= 0.0 {
+        return 0.0;
+    }
+    let mut hi = 1.0;
+    while moran(hi) > 0.0 {
+        hi *= 2.0;
+    }
+
+    let mut lo = 0.0;
+    for _ in 0..MORAN_ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if moran(mid) > 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    0.5 * (lo + hi)
+}
+
+/// Summary statistics of a point cloud
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttractorStats {
+    /// Mean point `(x, y)`
+    pub centroid: (f64, f64),
+
+    /// Population standard deviation along each axis
+    pub std_dev: (f64, f64),
+
+    /// Bounding box `(x_min, x_max, y_min, y_max)`
+    pub bounds: (f64, f64, f64, f64),
+}
+
+/// Compute the centroid, spread and bounding box of a point cloud
+///
+/// All statistics are accumulated in a single pass, using Welford's update
+/// for the mean and variance so that large orbits far from the origin do
+/// not lose precision.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates, as many as `xs`
+///
+/// # Returns
+///
+/// The statistics; an empty cloud has its centroid at the origin, no spread
+/// and an empty bounding box (infinite minima, negative infinite maxima)
+pub fn attractor_stats(xs: &[f64], ys: &[f64]) -> AttractorStats {
+    debug_assert_eq!(xs.len(), ys.len());
+
+    let mut mean = (0.0, 0.0);
+    let mut m2 = (0.0, 0.0);
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = EMPTY_BOUNDS;
+    let mut n = 0.0;
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        n += 1.0;
+        let dx = x - mean.0;
+        let dy = y - mean.1;
+        mean.0 += dx / n;
+        mean.1 += dy / n;
+        m2.0 += dx * (x - mean.0);
+        m2.1 += dy * (y - mean.1);
+
+        x_min = x_min.min(x);
+        x_max = x_max.max(x);
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+
+    let std_dev = if n > 0.0 {
+        ((m2.0 / n).sqrt(), (m2.1 / n).sqrt())
+    } else {
+        (0.0, 0.0)
+    };
+
+    AttractorStats {
+        centroid: mean,
+        std_dev,
+        bounds: (x_min, x_max, y_min, y_max),
+    }
+}
+
+/// Check whether a point cloud has collapsed to essentially a single point
+///
+/// Strongly contractive IFSs can have attractors whose extent is nonzero,
+/// so they normalize without errors, but so small that the orbit is one
+/// point for all practical purposes and renders as a trivial image. The
+/// spread is measured by the standard deviation along each axis rather than
+/// the bounding box, so the first points of an orbit, which have not yet
+/// converged to the attractor, barely affect it while they would dominate
+/// the extent.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates, as many as `xs`
+/// * `tol` - Smallest acceptable standard deviation along the widest axis
+///
+/// # Returns
+///
+/// `true` if the cloud is empty, contains non-finite values, or has a
+/// spread below `tol` along both axes
+pub fn is_degenerate(xs: &[f64], ys: &[f64], tol: f64) -> bool {
+    if xs.is_empty() {
+        return true;
+    }
+
+    let (sx, sy) = attractor_stats(xs, ys).std_dev;
+    !(sx.is_finite() && sy.is_finite() && sx.max(sy) >= tol)
+}
+
+/// Side length of the occupancy grid used by [`detect_symmetry`]
+pub const SYMMETRY_GRID_SIZE: usize = 64;
+
+/// Score above which [`detect_symmetry`] reports a symmetry
+pub const SYMMETRY_THRESHOLD: f64 = 0.9;
+
+/// Symmetries detected in a point cloud
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SymmetryReport {
+    /// Overlap in [0, 1] between the cloud and its mirror image across the
+    /// vertical axis through the center of its bounding box
+    pub flip_x_score: f64,
+
+    /// Overlap in [0, 1] between the cloud and its mirror image across the
+    /// horizontal axis through the center of its bounding box
+    pub flip_y_score: f64,
+
+    /// Overlap in [0, 1] between the cloud and its rotation by 180 degrees
+    /// about the center of its bounding box
+    pub rotation_score: f64,
+
+    /// Whether the cloud is symmetric under the x flip
+    pub flip_x: bool,
+
+    /// Whether the cloud is symmetric under the y flip
+    pub flip_y: bool,
+
+    /// Whether the cloud is symmetric under the 180 degree rotation
+    pub rotation: bool,
+}
+
+/// Detect approximate mirror and rotational symmetries of a point cloud
+///
+/// The cloud is binned into a `SYMMETRY_GRID_SIZE` square occupancy grid
+/// spanning its bounding box, and each symmetry is scored by the
+/// intersection over union of the grid and its transformed copy, so the
+/// tolerance is one grid cell. A symmetry is reported when its score is at
+/// least [`SYMMETRY_THRESHOLD`].
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+///
+/// # Returns
+///
+/// The scores and detected symmetries; all zero for an empty cloud or one
+/// without extent along either axis
+pub fn detect_symmetry(xs: &[f64], ys: &[f64]) -> SymmetryReport {
+    let n = SYMMETRY_GRID_SIZE;
+    let (x_min, x_max, y_min, y_max) = attractor_stats(xs, ys).bounds;
+    let x_extent = x_max - x_min;
+    let y_extent = y_max - y_min;
+    if !(x_extent > 0.0 && y_extent > 0.0 && x_extent.is_finite() && y_extent.is_finite()) {
+        return SymmetryReport::default();
+    }
+
+    // Occupancy grid over the bounding box
+    let mut grid = vec![false; n * n];
+    let cell = |v: f64, min: f64, extent: f64| {
+        (((v - min) / extent * n as f64).floor() as usize).min(n - 1)
+    };
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        grid[cell(y, y_min, y_extent) * n + cell(x, x_min, x_extent)] = true;
+    }
+
+    // Intersection over union of the grid and its image under a cell mapping
+    let score = |map: &dyn Fn(usize, usize) -> (usize, usize)| {
+        let mut intersection = 0;
+        let mut union = 0;
+        for row in 0..n {
+            for col in 0..n {
+                let (r, c) = map(row, col);
+                let a = grid[row * n + col];
+                let b = grid[r * n + c];
+                intersection += (a && b) as usize;
+                union += (a || b) as usize;
+            }
+        }
+        intersection as f64 / union as f64
+    };
+
+    let flip_x_score = score(&|row, col| (row, n - 1 - col));
+    let flip_y_score = score(&|row, col| (n - 1 - row, col));
+    let rotation_score = score(&|row, col| (n - 1 - row, n - 1 - col));
+
+    SymmetryReport {
+        flip_x_score,
+        flip_y_score,
+        rotation_score,
+        flip_x: flip_x_score >= SYMMETRY_THRESHOLD,
+        flip_y: flip_y_score >= SYMMETRY_THRESHOLD,
+        rotation: rotation_score >= SYMMETRY_THRESHOLD,
+    }
+}
@@ -0,0 +1,231 @@
+//! Animation support for the rust-random-logo library
+//!
+//! This module provides functions for morphing between two Iterated Function
+//! Systems and rendering the intermediate frames.
+//!
+//! Interpolation is performed by linearly interpolating the matrices,
+//! translations and weights of corresponding transformations. Linear
+//! interpolation of matrices is not geometrically perfect (an interpolated
+//! rotation may shrink midway), but it looks fine for short morphs.
+//!
+//! With the `gif` feature, [`render_gif`] renders a looping morph through
+//! several keyframes straight to an animated GIF.
+
+#[cfg(feature = "gif")]
+use std::fs::File;
+#[cfg(feature = "gif")]
+use std::io::BufWriter;
+#[cfg(feature = "gif")]
+use std::path::Path;
+
+#[cfg(feature = "gif")]
+use image::buffer::ConvertBuffer;
+#[cfg(feature = "gif")]
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::RgbImage;
+#[cfg(feature = "gif")]
+use image::{Delay, Frame};
+use rand::Rng;
+
+use crate::core::affine::Affine;
+#[cfg(feature = "gif")]
+use crate::core::cloud::PointCloud;
+use crate::core::config::Config;
+use crate::core::ifs::SigmaFactorIFS;
+use crate::core::renderer::try_render;
+#[cfg(feature = "gif")]
+use crate::core::renderer::{check_extent, union_bounds, EMPTY_BOUNDS};
+#[cfg(feature = "gif")]
+use crate::core::utils::seeded_rng;
+#[cfg(feature = "gif")]
+use crate::error::Error;
+use crate::error::Result;
+
+/// Delay between the frames of [`render_gif`] in milliseconds, 25 frames per second
+#[cfg(feature = "gif")]
+pub const GIF_FRAME_DELAY_MS: u32 = 40;
+
+/// Interpolate between two IFS with the same number of transformations
+///
+/// # Arguments
+///
+/// * `a` - The IFS at `t = 0`
+/// * `b` - The IFS at `t = 1`
+/// * `t` - The interpolation parameter, usually in [0, 1]
+///
+/// # Returns
+///
+/// A new SigmaFactorIFS whose transformations and weights are linearly
+/// interpolated between `a` and `b`
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have a different number of transformations
+pub fn interpolate_ifs(a: &SigmaFactorIFS, b: &SigmaFactorIFS, t: f64) -> SigmaFactorIFS {
+    assert_eq!(
+        a.transforms().len(),
+        b.transforms().len(),
+        "Interpolated IFS must have the same number of transforms"
+    );
+
+    let transforms = a
+        .transforms()
+        .iter()
+        .zip(b.transforms().iter())
+        .map(|(ta, tb)| Affine::new(ta.w + (tb.w - ta.w) * t, ta.b + (tb.b - ta.b) * t))
+        .collect();
+
+    // Interpolate and renormalize the weights
+    let mut weights: Vec<f64> = a
+        .weights()
+        .iter()
+        .zip(b.weights().iter())
+        .map(|(wa, wb)| wa + (wb - wa) * t)
+        .collect();
+    let sum: f64 = weights.iter().sum();
+    for w in &mut weights {
+        *w /= sum;
+    }
+
+    SigmaFactorIFS::new(transforms, weights)
+}
+
+/// Render the frames of a morph between two IFS
+///
+/// Every frame is rendered with a clone of the same random number generator,
+/// so that the orbit and color stay consistent from frame to frame.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `a` - The IFS of the first frame
+/// * `b` - The IFS of the last frame
+/// * `frames` - Number of frames to render
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing one RGB image per frame, or the Error of
+/// [`try_render`] for the first frame that cannot be rendered
+pub fn render_frames<R: Rng + Clone>(
+    rng: R,
+    a: &SigmaFactorIFS,
+    b: &SigmaFactorIFS,
+    frames: usize,
+    config: &Config,
+) -> Result<Vec<RgbImage>> {
+    (0..frames)
+        .map(|i| {
+            let t = if frames > 1 {
+                i as f64 / (frames - 1) as f64
+            } else {
+                0.0
+            };
+            let ifs = interpolate_ifs(a, b, t);
+            try_render(rng.clone(), &ifs, config)
+        })
+        .collect()
+}
+
+/// Render a looping morph through keyframes as an animated GIF
+///
+/// The animation interpolates from every keyframe to the next with
+/// [`interpolate_ifs`], and from the last keyframe back to the first, so it
+/// loops seamlessly; it has `keyframes.len() * frames_per_segment` frames,
+/// shown [`GIF_FRAME_DELAY_MS`] apart. Every frame is rendered with
+/// `seeded_rng(config.seed)`, so the orbit and color stay consistent, and
+/// with the same fixed bounds, so the attractor moves instead of being
+/// rescaled every frame: `config.fixed_bounds` if set, otherwise the union
+/// of the bounding boxes of all frames.
+///
+/// GIF frames hold at most 256 colors, so the encoder quantizes every frame
+/// to its own palette. The binary renderer draws two colors, which are kept
+/// exactly.
+///
+/// Every frame costs a full chaos game of `config.npoints` points, plus
+/// another one to find its bounds when `config.fixed_bounds` is not set,
+/// and the encoder quantizes every frame; a few seconds of animation are
+/// a hundred frames. Frames are encoded as they are rendered, so memory
+/// stays at one frame. Lower `npoints` or set `fixed_bounds` to render
+/// long animations faster.
+///
+/// # Arguments
+///
+/// * `keyframes` - The IFS to morph through, all with the same number of
+///   transformations
+/// * `frames_per_segment` - Number of frames from one keyframe to the next
+/// * `config` - Configuration for rendering every frame
+/// * `path` - Path of the GIF file to write
+///
+/// # Returns
+///
+/// A Result containing () if successful, a ConfigError if there are no
+/// keyframes or frames, an IfsError if the keyframes cannot be
+/// interpolated, a DegenerateError if the frames have no finite bounds, the
+/// Error of [`try_render`] for the first frame that cannot be rendered, or
+/// an Error if the file cannot be written
+#[cfg(feature = "gif")]
+pub fn render_gif<P: AsRef<Path>>(
+    keyframes: &[SigmaFactorIFS],
+    frames_per_segment: usize,
+    config: &Config,
+    path: P,
+) -> Result<()> {
+    config.validate()?;
+    if keyframes.is_empty() || frames_per_segment == 0 {
+        return Err(Error::ConfigError(format!(
+            "render_gif needs keyframes and frames, got {} keyframes and {} frames per segment",
+            keyframes.len(),
+            frames_per_segment
+        )));
+    }
+    let n = keyframes[0].transforms().len();
+    if keyframes.iter().any(|k| k.transforms().len() != n) {
+        return Err(Error::IfsError(
+            "Keyframes must all have the same number of transforms".to_string(),
+        ));
+    }
+
+    // Interpolate the IFS of every frame, closing the loop
+    let ifs_frames: Vec<SigmaFactorIFS> = (0..keyframes.len())
+        .flat_map(|k| {
+            let a = &keyframes[k];
+            let b = &keyframes[(k + 1) % keyframes.len()];
+            (0..frames_per_segment)
+                .map(move |i| interpolate_ifs(a, b, i as f64 / frames_per_segment as f64))
+        })
+        .collect();
+
+    let mut frame_config = config.clone();
+    if frame_config.fixed_bounds.is_none() {
+        let bounds = ifs_frames.iter().fold(EMPTY_BOUNDS, |bounds, ifs| {
+            let cloud = PointCloud::generate(&mut seeded_rng(config.seed), ifs, config);
+            union_bounds(bounds, cloud.bounds())
+        });
+        check_extent(bounds)?;
+        frame_config.fixed_bounds = Some(bounds);
+    }
+
+    // Frames are rendered as the encoder consumes them, stopping at the
+    // first frame that cannot be rendered
+    let delay = Delay::from_numer_denom_ms(GIF_FRAME_DELAY_MS, 1);
+    let mut error = None;
+    let frames = ifs_frames.iter().map_while(|ifs| {
+        match try_render(seeded_rng(config.seed), ifs, &frame_config) {
+            Ok(image) => Some(Frame::from_parts(image.convert(), 0, 0, delay)),
+            Err(err) => {
+                error = Some(err);
+                None
+            }
+        }
+    });
+
+    let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(frames)?;
+
+    match error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
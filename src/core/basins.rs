@@ -0,0 +1,74 @@
+//! Fixed-point basin rendering for the rust-random-logo library
+//!
+//! This module provides a visualization of how the transformations of an
+//! IFS partition its attractor: every plotted point is colored by the
+//! transformation whose fixed point is nearest, which gives a Voronoi-like
+//! partition of the attractor around the fixed points.
+
+use image::{ImageBuffer, Rgb, RgbImage};
+
+use crate::core::config::Config;
+use crate::core::ifs::SigmaFactorIFS;
+use crate::core::postprocess::apply_orientation;
+use crate::core::renderer::{generate_raw_points, normalize_points, plot_point};
+use crate::core::utils::{seeded_rng, JULIA_COLORS};
+
+/// Render the attractor colored by the nearest fixed point
+///
+/// The orbit is generated from `seeded_rng(config.seed)`, and each point is
+/// given the Julia color of the transformation (cycling through the colors
+/// by index) whose fixed point is nearest to it in the plane, before
+/// normalization. Transformations without a fixed point are excluded from
+/// the partition. The projection, region of interest and color scheme of
+/// the configuration are not applied.
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image; blank if no transformation has a fixed point
+pub fn render_basins(ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    let height = config.height;
+    let width = config.width;
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, Rgb(config.background));
+
+    let fixed_points: Vec<(usize, f64, f64)> = ifs
+        .transforms()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| t.fixed_point().map(|p| (i, p.x, p.y)))
+        .collect();
+    if fixed_points.is_empty() {
+        return image;
+    }
+
+    // Assign every raw point to its nearest fixed point
+    let mut rng = seeded_rng(config.seed);
+    let (mut xs, mut ys) = generate_raw_points(&mut rng, ifs, config.npoints);
+    let labels: Vec<usize> = xs
+        .iter()
+        .zip(ys.iter())
+        .map(|(&x, &y)| {
+            let distance = |&(_, fx, fy): &(usize, f64, f64)| (x - fx).hypot(y - fy);
+            fixed_points
+                .iter()
+                .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                .unwrap()
+                .0
+        })
+        .collect();
+
+    // Draw points
+    normalize_points(&mut xs, &mut ys, height, width);
+    for ((&x, &y), &label) in xs.iter().zip(ys.iter()).zip(labels.iter()) {
+        let color = JULIA_COLORS[label % JULIA_COLORS.len()];
+        plot_point(&mut image, x, y, color, config.point_size);
+    }
+
+    apply_orientation(&mut image, config);
+
+    image
+}
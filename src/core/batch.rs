@@ -0,0 +1,232 @@
+//! Batch rendering for the rust-random-logo library
+//!
+//! This module provides functions for rendering many fractals, either to a
+//! directory in a way that can be resumed after an interruption, or tiled
+//! into a single grid image.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::{imageops, ImageBuffer, ImageFormat, Rgb, RgbImage};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::core::analysis::{coverage, similarity_dimension};
+use crate::core::config::Config;
+use crate::core::renderer::{ifs_from_config, render_from_config, try_render};
+use crate::core::utils::seeded_rng;
+use crate::error::{Error, Result};
+
+/// Summary of a batch run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchSummary {
+    /// Number of images rendered during this run
+    pub generated: usize,
+
+    /// Number of images skipped because their output file already existed or
+    /// their attractor was degenerate after all retries
+    pub skipped: usize,
+}
+
+/// Statistics of the fractal of one seed, passed to the predicate of [`find_seeds`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeedResult {
+    /// The seed of the fractal
+    pub seed: u64,
+
+    /// Fraction of pixels that differ from the background
+    pub coverage: f64,
+
+    /// Similarity dimension of the IFS
+    pub similarity_dimension: f64,
+
+    /// Number of transformations of the IFS
+    pub num_transforms: usize,
+}
+
+/// Scan consecutive seeds for fractals accepted by a predicate
+///
+/// Seeds `start_seed`, `start_seed + 1`, ... are scanned in order. Each
+/// seed's IFS is sampled from `seeded_rng(seed)` and rendered with
+/// [`try_render`], without the retries of
+/// [`render_from_config`], so every accepted seed reproduces its fractal
+/// with `render(rng, &ifs, &config)`. Seeds whose orbit diverges are
+/// skipped without calling the predicate. The scan is sequential and
+/// deterministic: the same arguments always return the same seeds.
+///
+/// # Arguments
+///
+/// * `base_config` - Configuration shared by all seeds; its seed is overridden
+/// * `start_seed` - The first seed to scan
+/// * `count` - Number of accepted seeds after which the scan stops
+/// * `max_scan` - Maximum number of seeds to scan
+/// * `predicate` - Called with the statistics of every rendered seed;
+///   returns whether to accept it
+///
+/// # Returns
+///
+/// A Result containing the accepted seeds in scan order, fewer than `count`
+/// if `max_scan` was hit, or an Error if the configuration is invalid
+pub fn find_seeds<F: FnMut(&SeedResult) -> bool>(
+    base_config: &Config,
+    start_seed: u64,
+    count: usize,
+    max_scan: usize,
+    mut predicate: F,
+) -> Result<Vec<u64>> {
+    base_config.validate()?;
+
+    let mut found = Vec::new();
+    for offset in 0..max_scan as u64 {
+        if found.len() >= count {
+            break;
+        }
+
+        let mut config = base_config.clone();
+        config.seed = start_seed.wrapping_add(offset);
+        let mut rng = seeded_rng(config.seed);
+        let ifs = ifs_from_config(&mut rng, &config);
+        let Ok(image) = try_render(rng, &ifs, &config) else {
+            continue;
+        };
+
+        let result = SeedResult {
+            seed: config.seed,
+            coverage: coverage(&image, config.background),
+            similarity_dimension: similarity_dimension(&ifs),
+            num_transforms: ifs.transforms().len(),
+        };
+        if predicate(&result) {
+            found.push(config.seed);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Get the output path of the image rendered for a seed
+///
+/// # Arguments
+///
+/// * `out_dir` - The output directory
+/// * `seed` - The seed of the image
+///
+/// # Returns
+///
+/// The path `out_dir/fractal_<seed>.png`
+pub fn batch_output_path<P: AsRef<Path>>(out_dir: P, seed: u64) -> PathBuf {
+    out_dir.as_ref().join(format!("fractal_{}.png", seed))
+}
+
+/// Render one image per seed into a directory, skipping existing outputs
+///
+/// Each image is written as soon as it is rendered. Images are first written
+/// to a temporary file and then renamed, so an interrupted run never leaves a
+/// truncated PNG behind, and running the same batch again resumes where it
+/// stopped. Seeds whose attractor is degenerate are skipped without writing
+/// anything.
+///
+/// # Arguments
+///
+/// * `base_config` - Configuration shared by all images; its seed is overridden
+/// * `seeds` - The seeds to render
+/// * `out_dir` - The output directory, created if it does not exist
+///
+/// # Returns
+///
+/// A Result containing a BatchSummary if successful, or an Error if the
+/// configuration is invalid or an image cannot be written
+pub fn render_batch_to_dir<P: AsRef<Path>>(
+    base_config: &Config,
+    seeds: &[u64],
+    out_dir: P,
+) -> Result<BatchSummary> {
+    let out_dir = out_dir.as_ref();
+    fs::create_dir_all(out_dir)?;
+
+    let mut summary = BatchSummary::default();
+    for &seed in seeds {
+        let path = batch_output_path(out_dir, seed);
+        if path.exists() {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let mut config = base_config.clone();
+        config.seed = seed;
+        let image = match render_from_config(&config) {
+            Ok(image) => image,
+            Err(Error::DegenerateError(_)) => {
+                summary.skipped += 1;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        let tmp_path = path.with_extension("png.tmp");
+        image.save_with_format(&tmp_path, ImageFormat::Png)?;
+        fs::rename(&tmp_path, &path)?;
+        summary.generated += 1;
+    }
+
+    Ok(summary)
+}
+
+/// Render a grid of fractals tiled into a single image
+///
+/// The tile at `(row, col)` has index `row * cols + col` and is rendered with
+/// the seed `seed_base + index`, which drives both its IFS and its color.
+/// With the `parallel` feature, tiles are rendered concurrently; the result
+/// does not depend on it. Tiles whose attractor is degenerate are left
+/// blank, filled with the background color.
+///
+/// # Arguments
+///
+/// * `base_config` - Configuration of every tile; its seed is overridden
+/// * `rows` - Number of rows of the grid
+/// * `cols` - Number of columns of the grid
+/// * `seed_base` - Seed of the first tile
+///
+/// # Returns
+///
+/// A Result containing an RGB image of size `cols * width` by
+/// `rows * height`, or a ConfigError if the configuration is invalid
+pub fn render_grid(
+    base_config: &Config,
+    rows: usize,
+    cols: usize,
+    seed_base: u64,
+) -> Result<RgbImage> {
+    base_config.validate()?;
+
+    let render_tile = |index: usize| {
+        let mut config = base_config.clone();
+        config.seed = seed_base.wrapping_add(index as u64);
+        let mut rng = seeded_rng(config.seed);
+        let ifs = ifs_from_config(&mut rng, &config);
+        try_render(rng, &ifs, &config).ok()
+    };
+
+    #[cfg(feature = "parallel")]
+    let tiles: Vec<Option<RgbImage>> = (0..rows * cols).into_par_iter().map(render_tile).collect();
+    #[cfg(not(feature = "parallel"))]
+    let tiles: Vec<Option<RgbImage>> = (0..rows * cols).map(render_tile).collect();
+
+    let width = base_config.width;
+    let height = base_config.height;
+    let mut grid = ImageBuffer::from_pixel(
+        (width * cols) as u32,
+        (height * rows) as u32,
+        Rgb(base_config.background),
+    );
+    for (index, tile) in tiles.iter().enumerate() {
+        let Some(tile) = tile else {
+            continue;
+        };
+        let x = (index % cols * width) as i64;
+        let y = (index / cols * height) as i64;
+        imageops::replace(&mut grid, tile, x, y);
+    }
+
+    Ok(grid)
+}
@@ -5,10 +5,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 use toml;
 
-use crate::error::Result;
+use crate::core::ifs::ifs_from_coefficients;
+use crate::core::types::{ColorScheme, Projection, Symmetry, ToneMap};
+use crate::error::{Error, Result};
 
 /// Configuration for generating fractal images
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +36,230 @@ pub struct Config {
 
     /// Seed for the random number generator
     pub seed: u64,
+
+    /// Projection applied to the orbit before rasterization
+    #[serde(default)]
+    pub projection: Projection,
+
+    /// Background color the canvas is filled with before plotting
+    #[serde(default)]
+    pub background: [u8; 3],
+
+    /// Radius in pixels of the square plotted for each point (0 plots a single pixel)
+    #[serde(default)]
+    pub point_size: u32,
+
+    /// Tone mapping operator used by density rendering
+    #[serde(default)]
+    pub tone_map: ToneMap,
+
+    /// Mirror the rendered image horizontally
+    #[serde(default)]
+    pub flip_x: bool,
+
+    /// Mirror the rendered image vertically, so that y grows upward
+    #[serde(default)]
+    pub flip_y: bool,
+
+    /// Dither density output before quantizing it to 8 bits
+    #[serde(default)]
+    pub dither: bool,
+
+    /// Equalize the density histogram instead of applying `tone_map`
+    #[serde(default)]
+    pub equalize: bool,
+
+    /// Number of transformations of the generated IFS (random 2 to 4 if unset)
+    #[serde(default)]
+    pub num_transforms: Option<usize>,
+
+    /// Region `(x0, x1, y0, y1)` of the canvas, in fractions of its size, that
+    /// is zoomed to fill the whole image
+    #[serde(default)]
+    pub roi: Option<(f64, f64, f64, f64)>,
+
+    /// Fraction of the canvas the binary rendering should cover, chosen
+    /// by adjusting the point size (overrides `point_size` when set)
+    #[serde(default)]
+    pub target_coverage: Option<f64>,
+
+    /// Number of times a degenerate attractor is regenerated with the next
+    /// seed (`seed + 1`, `seed + 2`, ...) before giving up
+    #[serde(default)]
+    pub max_retries: usize,
+
+    /// How points of the binary rendering are colored
+    #[serde(default)]
+    pub color_scheme: ColorScheme,
+
+    /// Radius around the origin the orbit is confined to; a point leaving
+    /// it is replaced by the previous point of the orbit
+    #[serde(default)]
+    pub clamp_radius: Option<f64>,
+
+    /// Perturb every point by up to half a pixel with seeded noise before
+    /// rasterizing, which breaks up grid-aligned aliasing
+    #[serde(default)]
+    pub jitter: bool,
+
+    /// World rectangle `(x_min, x_max, y_min, y_max)` mapped to the canvas instead
+    /// of the bounding box of the orbit; points outside it are clipped
+    #[serde(default)]
+    pub fixed_bounds: Option<(f64, f64, f64, f64)>,
+
+    /// Bits per channel of saved density images (8 or 16)
+    #[serde(default = "default_bit_depth")]
+    pub bit_depth: u8,
+
+    /// Plot points in a seeded random order instead of iteration order,
+    /// which changes which color shows in pixels visited more than once
+    /// but not the geometry
+    #[serde(default)]
+    pub shuffle_draw: bool,
+
+    /// Explicit transformations `[a, b, c, d, e, f]`, each the map
+    /// `(x, y) -> (a * x + b * y + e, c * x + d * y + f)`, used instead of a random IFS
+    #[serde(default)]
+    pub transforms: Option<Vec<[f64; 6]>>,
+
+    /// Selection weights of the explicit `transforms` (proportional to the
+    /// absolute determinants if unset)
+    #[serde(default)]
+    pub weights: Option<Vec<f64>>,
+
+    /// Plot only every `plot_stride`-th point of the orbit (1 plots all of them)
+    ///
+    /// Unlike lowering `npoints`, the chaos game still runs for all `npoints`
+    /// iterations, so the plotted points sample the whole attractor as
+    /// thoroughly as the full orbit does, only more sparsely
+    #[serde(default = "default_plot_stride")]
+    pub plot_stride: usize,
+
+    /// Stroke width in pixels of the polygons drawn by `skeleton_to_svg`
+    #[serde(default = "default_stroke_width")]
+    pub stroke_width: f64,
+
+    /// Renderer used by `render_from_config`: `"binary"` plots points, `"density"`
+    /// tone-maps hit counts, `"trails"` connects consecutive points,
+    /// `"skeleton"` draws the first-level wireframe of the IFS, `"splat"`
+    /// accumulates a Gaussian of `splat_sigma` pixels around every point and
+    /// `"distance"` colors pixels by their distance to the attractor
+    #[serde(default = "default_render_mode")]
+    pub render_mode: String,
+
+    /// Symmetry imposed on the orbit, applied to the raw points so that the
+    /// symmetrized cloud as a whole is normalized to the canvas
+    #[serde(default)]
+    pub symmetry: Option<Symmetry>,
+
+    /// Number of candidates rendered by `render_from_config`, which returns the
+    /// one with the highest coverage times similarity dimension
+    #[serde(default)]
+    pub best_of: Option<usize>,
+
+    /// Opacity in [0, 1] of every point plotted by `render_rgba`, clamped to
+    /// that range. Overlapping points build up toward opaque, so lower values
+    /// with more points give smoother gradients
+    #[serde(default = "default_point_alpha")]
+    pub point_alpha: f64,
+
+    /// Standard deviation in pixels of the Gaussian splats of the `"splat"`
+    /// render mode
+    #[serde(default = "default_splat_sigma")]
+    pub splat_sigma: f64,
+
+    /// Largest memory budget in bytes of a rendering, as estimated by
+    /// `estimated_memory_bytes`, checked by `validate` so that oversized
+    /// configurations fail cleanly instead of running out of memory
+    #[serde(default = "default_max_memory_bytes")]
+    pub max_memory_bytes: u64,
+
+    /// Distance in pixels from the attractor at which the halos of the
+    /// `"distance"` render mode fade into the background
+    #[serde(default = "default_halo_radius")]
+    pub halo_radius: f64,
+
+    /// Standard deviation in pixels of a Gaussian blur applied to the image
+    /// rendered by `render_from_config`, softening jagged edges; 0 disables it
+    #[serde(default)]
+    pub post_blur: f64,
+
+    /// Number of subpixels per pixel along each axis of the `"binary"` render
+    /// mode; points are accumulated on the finer grid with fixed-point
+    /// coordinates and every pixel averages its subpixels, which anti-aliases
+    /// the edges. 1 plots whole pixels
+    #[serde(default = "default_supersample")]
+    pub supersample: usize,
+}
+
+/// Default number of bits per channel of saved images
+fn default_bit_depth() -> u8 {
+    8
+}
+
+/// Default stride between plotted points
+fn default_plot_stride() -> usize {
+    1
+}
+
+/// Default stroke width of SVG skeletons
+fn default_stroke_width() -> f64 {
+    1.0
+}
+
+/// Default opacity of points plotted in RGBA
+fn default_point_alpha() -> f64 {
+    1.0
+}
+
+/// Default standard deviation of Gaussian splats
+fn default_splat_sigma() -> f64 {
+    1.0
+}
+
+/// Default fade-out distance of distance-field halos
+fn default_halo_radius() -> f64 {
+    16.0
+}
+
+/// Default supersampling factor, plotting whole pixels
+fn default_supersample() -> usize {
+    1
+}
+
+/// Largest number of rotations accepted in `Symmetry::Rotational`
+///
+/// Every rotation adds a copy of the orbit, so the point buffers grow
+/// linearly with it.
+pub const MAX_ROTATIONAL_SYMMETRY: u32 = 64;
+
+/// Largest supersampling factor accepted in `Config::supersample`
+pub const MAX_SUPERSAMPLE: usize = 16;
+
+/// Default memory budget of a rendering, 4 GiB
+pub const DEFAULT_MAX_MEMORY_BYTES: u64 = 4 << 30;
+
+/// Default memory budget of a rendering
+fn default_max_memory_bytes() -> u64 {
+    DEFAULT_MAX_MEMORY_BYTES
+}
+
+/// Render modes accepted in `Config::render_mode`
+pub const RENDER_MODES: [&str; 6] = [
+    "binary", "density", "trails", "skeleton", "splat", "distance",
+];
+
+/// Default render mode
+fn default_render_mode() -> String {
+    "binary".to_string()
+}
+
+/// Array of configurations read by `Config::many_from_file`
+#[derive(Deserialize)]
+struct Jobs {
+    /// One configuration per `[[job]]` table
+    #[serde(default)]
+    job: Vec<Config>,
 }
 
 impl Config {
@@ -46,7 +273,290 @@ impl Config {
             ndims: 2,
             rng_name: "Xoshiro256PlusPlus".to_string(),
             seed: 99,
+            projection: Projection::Cartesian,
+            background: [0, 0, 0],
+            point_size: 0,
+            tone_map: ToneMap::Log,
+            flip_x: false,
+            flip_y: false,
+            dither: false,
+            equalize: false,
+            num_transforms: None,
+            roi: None,
+            target_coverage: None,
+            max_retries: 0,
+            color_scheme: ColorScheme::Single,
+            clamp_radius: None,
+            jitter: false,
+            fixed_bounds: None,
+            bit_depth: 8,
+            shuffle_draw: false,
+            transforms: None,
+            weights: None,
+            plot_stride: 1,
+            stroke_width: 1.0,
+            render_mode: "binary".to_string(),
+            symmetry: None,
+            best_of: None,
+            point_alpha: 1.0,
+            splat_sigma: 1.0,
+            max_memory_bytes: DEFAULT_MAX_MEMORY_BYTES,
+            halo_radius: 16.0,
+            post_blur: 0.0,
+            supersample: 1,
+        }
+    }
+
+    /// Estimate the peak memory of rendering with this configuration
+    ///
+    /// Every point of the orbit costs 32 bytes: two coordinates, the index
+    /// of its transformation and its place in the draw order. The orbit is
+    /// copied once per image of `symmetry`. Every pixel costs 3 bytes for
+    /// the image plus the working buffers of the render mode and color
+    /// scheme: 20 bytes for the density and splat modes, 15 for the
+    /// distance mode, 4 per transformation and 4 more for the
+    /// `WeightedTransform` color scheme, 48 for `post_blur`, and 4 per
+    /// subpixel for a `supersample` factor above 1.
+    ///
+    /// The estimate is approximate; it bounds the large buffers, not every
+    /// small allocation.
+    ///
+    /// # Returns
+    ///
+    /// The estimate in bytes, or `None` if it overflows a `u64`
+    pub fn estimated_memory_bytes(&self) -> Option<u64> {
+        let copies: u64 = match self.symmetry {
+            None => 1,
+            Some(Symmetry::MirrorX) | Some(Symmetry::MirrorY) => 2,
+            Some(Symmetry::Rotational(n)) => n as u64,
+        };
+        let points = (self.npoints as u64).checked_mul(32)?.checked_mul(copies)?;
+
+        let mut per_pixel: u64 = 3;
+        per_pixel += match self.render_mode.as_str() {
+            "density" | "splat" => 20,
+            "distance" => 15,
+            _ => 0,
+        };
+        if self.color_scheme == ColorScheme::WeightedTransform {
+            let transforms = match (&self.transforms, self.num_transforms) {
+                (Some(transforms), _) => transforms.len(),
+                (None, Some(n)) => n,
+                (None, None) => 4,
+            };
+            per_pixel =
+                per_pixel.checked_add((transforms as u64).checked_mul(4)?.checked_add(4)?)?;
+        }
+        if self.post_blur > 0.0 {
+            per_pixel += 48;
         }
+        if self.supersample > 1 {
+            let subpixels = (self.supersample as u64).checked_mul(self.supersample as u64)?;
+            per_pixel = per_pixel.checked_add(subpixels.checked_mul(4)?)?;
+        }
+        let pixels = (self.width as u64)
+            .checked_mul(self.height as u64)?
+            .checked_mul(per_pixel)?;
+
+        points.checked_add(pixels)
+    }
+
+    /// Check that the configuration describes a renderable fractal
+    ///
+    /// # Returns
+    ///
+    /// A Result containing () if the configuration is valid, or a ConfigError
+    /// describing the first invalid field
+    pub fn validate(&self) -> Result<()> {
+        if self.ifs_name != "SigmaFactorIFS" {
+            return Err(Error::ConfigError(format!(
+                "Unknown IFS: {}",
+                self.ifs_name
+            )));
+        }
+
+        if self.ndims != 2 {
+            return Err(Error::ConfigError(format!(
+                "Unsupported dimension: {}",
+                self.ndims
+            )));
+        }
+
+        if self.rng_name != "Xoshiro256PlusPlus" {
+            return Err(Error::ConfigError(format!(
+                "Unknown RNG: {}",
+                self.rng_name
+            )));
+        }
+
+        if let Some(n) = self.num_transforms {
+            if n < 2 {
+                return Err(Error::ConfigError(format!(
+                    "num_transforms must be at least 2, got {}",
+                    n
+                )));
+            }
+        }
+
+        match (&self.transforms, &self.weights) {
+            (Some(transforms), weights) => {
+                ifs_from_coefficients(transforms, weights.as_deref())?;
+            }
+            (None, Some(_)) => {
+                return Err(Error::ConfigError(
+                    "weights require explicit transforms".to_string(),
+                ));
+            }
+            (None, None) => {}
+        }
+
+        if self.bit_depth != 8 && self.bit_depth != 16 {
+            return Err(Error::ConfigError(format!(
+                "bit_depth must be 8 or 16, got {}",
+                self.bit_depth
+            )));
+        }
+
+        if self.best_of == Some(0) {
+            return Err(Error::ConfigError("best_of must be at least 1".to_string()));
+        }
+
+        self.validate_rendering()?;
+
+        if self
+            .estimated_memory_bytes()
+            .is_none_or(|bytes| bytes > self.max_memory_bytes)
+        {
+            return Err(Error::ConfigError(format!(
+                "npoints = {} and a {}x{} image exceed the memory budget of {} bytes",
+                self.npoints, self.width, self.height, self.max_memory_bytes
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Check the fields that affect how a given IFS is rendered
+    ///
+    /// This is the part of [`validate`](Self::validate) that applies when the
+    /// random number generator and IFS are supplied by the caller, as in
+    /// [`try_render`](crate::try_render): the choice of generator and IFS,
+    /// the output bit depth and the memory budget are not checked.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing () if the fields are valid, or a ConfigError
+    /// describing the first invalid field
+    pub(crate) fn validate_rendering(&self) -> Result<()> {
+        if self.npoints == 0 {
+            return Err(Error::ConfigError("npoints must be at least 1".to_string()));
+        }
+
+        if let Some((x0, x1, y0, y1)) = self.roi {
+            if !(x0 < x1 && y0 < y1) {
+                return Err(Error::ConfigError(format!(
+                    "roi must satisfy x0 < x1 and y0 < y1, got {:?}",
+                    (x0, x1, y0, y1)
+                )));
+            }
+        }
+
+        if let Some((x_min, x_max, y_min, y_max)) = self.fixed_bounds {
+            if !(x_min < x_max
+                && y_min < y_max
+                && (x_max - x_min).is_finite()
+                && (y_max - y_min).is_finite())
+            {
+                return Err(Error::ConfigError(format!(
+                    "fixed_bounds must be finite with x_min < x_max and y_min < y_max, got {:?}",
+                    (x_min, x_max, y_min, y_max)
+                )));
+            }
+        }
+
+        if !RENDER_MODES.contains(&self.render_mode.as_str()) {
+            return Err(Error::ConfigError(format!(
+                "Unknown render mode: {} (expected one of {})",
+                self.render_mode,
+                RENDER_MODES.join(", ")
+            )));
+        }
+
+        if let Some(Symmetry::Rotational(n)) = self.symmetry {
+            if !(1..=MAX_ROTATIONAL_SYMMETRY).contains(&n) {
+                return Err(Error::ConfigError(format!(
+                    "Rotational symmetry must have between 1 and {} rotations, got {}",
+                    MAX_ROTATIONAL_SYMMETRY, n
+                )));
+            }
+        }
+
+        if !(1..=MAX_SUPERSAMPLE).contains(&self.supersample) {
+            return Err(Error::ConfigError(format!(
+                "supersample must be between 1 and {}, got {}",
+                MAX_SUPERSAMPLE, self.supersample
+            )));
+        }
+
+        if !(self.post_blur >= 0.0 && self.post_blur.is_finite()) {
+            return Err(Error::ConfigError(format!(
+                "post_blur must be non-negative and finite, got {}",
+                self.post_blur
+            )));
+        }
+
+        if !(self.halo_radius > 0.0 && self.halo_radius.is_finite()) {
+            return Err(Error::ConfigError(format!(
+                "halo_radius must be positive and finite, got {}",
+                self.halo_radius
+            )));
+        }
+
+        if !(self.splat_sigma > 0.0 && self.splat_sigma.is_finite()) {
+            return Err(Error::ConfigError(format!(
+                "splat_sigma must be positive and finite, got {}",
+                self.splat_sigma
+            )));
+        }
+
+        if self.point_alpha.is_nan() {
+            return Err(Error::ConfigError(
+                "point_alpha must be a number".to_string(),
+            ));
+        }
+
+        if self.plot_stride == 0 {
+            return Err(Error::ConfigError(
+                "plot_stride must be at least 1".to_string(),
+            ));
+        }
+
+        if !(self.stroke_width > 0.0 && self.stroke_width.is_finite()) {
+            return Err(Error::ConfigError(format!(
+                "stroke_width must be positive and finite, got {}",
+                self.stroke_width
+            )));
+        }
+
+        if let Some(radius) = self.clamp_radius {
+            if radius.is_nan() || radius <= 0.0 {
+                return Err(Error::ConfigError(format!(
+                    "clamp_radius must be positive, got {}",
+                    radius
+                )));
+            }
+        }
+
+        if let Some(target) = self.target_coverage {
+            if !(target > 0.0 && target <= 1.0) {
+                return Err(Error::ConfigError(format!(
+                    "target_coverage must be in (0, 1], got {}",
+                    target
+                )));
+            }
+        }
+
+        Ok(())
     }
 
     /// Load configuration from a TOML file
@@ -64,6 +574,88 @@ impl Config {
         Ok(config)
     }
 
+    /// Load several configurations from a TOML file of `[[job]]` tables
+    ///
+    /// Each `[[job]]` table holds the same keys as a single configuration
+    /// file, which lets a whole batch be specified in one file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the TOML file
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the Configs in file order if successful, or an
+    /// Error if not
+    pub fn many_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        let content = fs::read_to_string(path)?;
+        let jobs: Jobs = toml::from_str(&content)?;
+        Ok(jobs.job)
+    }
+
+    /// Load configuration from a reader containing TOML
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader to read the TOML from, e.g. stdin
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the Config if successful, or an Error if not
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        let config: Self = toml::from_str(&content)?;
+        Ok(config)
+    }
+
+    /// Expand placeholders in a template such as an output file name
+    ///
+    /// The supported placeholders are `{seed}`, `{width}`, `{height}` and
+    /// `{npoints}`. Any other text, including unknown or unterminated braces,
+    /// is kept literally.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template, e.g. `fractal_{seed}_{width}x{height}.png`
+    ///
+    /// # Returns
+    ///
+    /// The template with every known placeholder replaced
+    pub fn expand_template(&self, template: &str) -> String {
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after.find('}').and_then(|end| {
+                let value = match &after[..end] {
+                    "seed" => self.seed.to_string(),
+                    "width" => self.width.to_string(),
+                    "height" => self.height.to_string(),
+                    "npoints" => self.npoints.to_string(),
+                    _ => return None,
+                };
+                Some((value, end))
+            });
+
+            match value {
+                Some((value, end)) => {
+                    result.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    result.push('{');
+                    rest = after;
+                }
+            }
+        }
+        result.push_str(rest);
+
+        result
+    }
+
     /// Save configuration to a TOML file
     ///
     /// # Arguments
@@ -0,0 +1,548 @@
+//! Density rendering for the rust-random-logo library
+//!
+//! This module provides functions for accumulating per-pixel hit counts of
+//! the chaos game and converting them to an image through a tone mapping
+//! operator, which reveals how often each region of the attractor is visited.
+
+use std::path::Path;
+
+use image::{GrayImage, ImageBuffer, Rgb, RgbImage};
+use rand::Rng;
+
+use crate::core::config::Config;
+use crate::core::ifs::SigmaFactorIFS;
+use crate::core::postprocess::apply_orientation;
+use crate::core::renderer::{
+    any_on_canvas, ifs_from_config, prepare_points, Rendered, DITHER_STREAM,
+};
+use crate::core::types::ToneMap;
+use crate::core::utils::{aux_rng, random_julia_color, seeded_rng};
+use crate::error::{Error, Result};
+
+/// Accumulate per-pixel hit counts of the chaos game
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// Row-major hit counts of size `config.width * config.height`
+pub fn density_buffer<R: Rng>(rng: &mut R, ifs: &SigmaFactorIFS, config: &Config) -> Vec<u32> {
+    density_buffer_with_bounds(rng, ifs, config).0
+}
+
+/// Accumulate per-pixel hit counts and report the raw bounding box of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A tuple of the row-major hit counts and the bounding box
+/// `(x_min, x_max, y_min, y_max)` that was mapped to the canvas
+fn density_buffer_with_bounds<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> (Vec<u32>, (f64, f64, f64, f64)) {
+    let height = config.height;
+    let width = config.width;
+
+    // Generate points
+    let points = prepare_points(rng, ifs, config);
+
+    // Count hits
+    let mut counts = vec![0u32; width * height];
+    for (x, y) in points.xs.iter().zip(points.ys.iter()) {
+        let x = x.trunc() as usize;
+        let y = y.trunc() as usize;
+
+        // Check bounds
+        if x < width && y < height {
+            counts[y * width + x] += 1;
+        }
+    }
+
+    (counts, points.bounds)
+}
+
+/// Map hit counts to intensities in [0, 1]
+///
+/// # Arguments
+///
+/// * `counts` - Per-pixel hit counts
+/// * `tone_map` - The tone mapping operator
+///
+/// # Returns
+///
+/// Per-pixel intensities, where the most visited pixel maps to 1
+pub fn tone_map(counts: &[u32], tone_map: ToneMap) -> Vec<f32> {
+    let max = counts.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return vec![0.0; counts.len()];
+    }
+    let max = max as f64;
+
+    // Reinhard is applied to counts relative to the mean visited count
+    let visited = counts.iter().filter(|&&c| c > 0).count() as f64;
+    let mean = counts.iter().map(|&c| c as f64).sum::<f64>() / visited;
+    let reinhard = |c: f64| {
+        let x = c / mean;
+        x / (1.0 + x)
+    };
+
+    counts
+        .iter()
+        .map(|&c| {
+            let c = c as f64;
+            let v = match tone_map {
+                ToneMap::Linear => c / max,
+                ToneMap::Log => c.ln_1p() / max.ln_1p(),
+                ToneMap::Sqrt => (c / max).sqrt(),
+                ToneMap::Reinhard => reinhard(c) / reinhard(max),
+                ToneMap::Binary => (c > 0.0) as u8 as f64,
+            };
+            v as f32
+        })
+        .collect()
+}
+
+/// Number of levels visited pixels are spread over by [`equalize`]
+pub const EQUALIZE_LEVELS: u32 = 65_535;
+
+/// Equalize the histogram of a density buffer in place
+///
+/// Every visited pixel is replaced by its rank in the cumulative
+/// distribution of visited counts, scaled to [1, `EQUALIZE_LEVELS`], so the
+/// output levels are approximately uniformly distributed. Unvisited pixels
+/// stay at 0. This reveals faint filaments that logarithmic tone mapping
+/// still crushes.
+///
+/// # Arguments
+///
+/// * `counts` - Per-pixel hit counts
+pub fn equalize(counts: &mut [u32]) {
+    let mut sorted: Vec<u32> = counts.iter().copied().filter(|&c| c > 0).collect();
+    if sorted.is_empty() {
+        return;
+    }
+    sorted.sort_unstable();
+    let total = sorted.len() as f64;
+
+    for c in counts.iter_mut().filter(|c| **c > 0) {
+        // Fraction of visited pixels with a count less than or equal to c
+        let rank = sorted.partition_point(|&v| v <= *c) as f64;
+        *c = ((rank / total) * EQUALIZE_LEVELS as f64).round().max(1.0) as u32;
+    }
+}
+
+/// Convert a density buffer to intensities as configured
+///
+/// The counts are equalized if `config.equalize` is set, then tone-mapped
+/// with `config.tone_map`. Equalized levels are already evenly spread, so
+/// they are mapped linearly instead.
+///
+/// # Arguments
+///
+/// * `counts` - Per-pixel hit counts
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// Per-pixel intensities in [0, 1]
+fn intensities(mut counts: Vec<u32>, config: &Config) -> Vec<f32> {
+    if config.equalize {
+        equalize(&mut counts);
+        tone_map(&counts, ToneMap::Linear)
+    } else {
+        tone_map(&counts, config.tone_map)
+    }
+}
+
+/// Render an image whose brightness follows the density of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image blending from the background color to a Julia color
+pub fn render_density<R: Rng + Clone>(rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    render_density_with_bounds(rng, ifs, config).image
+}
+
+/// Render a density image and report the raw bounding box of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+pub(crate) fn render_density_with_bounds<R: Rng + Clone>(
+    mut rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Rendered {
+    let (counts, bounds) = density_buffer_with_bounds(&mut rng, ifs, config);
+    let plotted = counts.iter().any(|&c| c > 0);
+
+    let intensities = intensities(counts, config);
+    let color = random_julia_color(&mut rng);
+
+    Rendered {
+        image: intensity_image(&intensities, config, color),
+        bounds,
+        color: Some(color),
+        plotted,
+    }
+}
+
+/// Render an image by accumulating a Gaussian splat around every point
+///
+/// Every point adds a 2D Gaussian kernel of standard deviation
+/// `config.splat_sigma` pixels, truncated at three standard deviations and
+/// computed once, into a floating-point buffer; contributions falling
+/// outside the image are dropped. Brightness follows the logarithm of the
+/// accumulated weight, relative to the brightest pixel, which gives soft,
+/// glowing attractors at the cost of `(6 * sigma + 1)^2` updates per point.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image blending from the background color to a Julia color
+pub fn render_splat<R: Rng + Clone>(rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    render_splat_with_bounds(rng, ifs, config).image
+}
+
+/// Render a splat image and report the raw bounding box of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+pub(crate) fn render_splat_with_bounds<R: Rng + Clone>(
+    mut rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Rendered {
+    let height = config.height as i64;
+    let width = config.width as i64;
+
+    // Generate points
+    let points = prepare_points(&mut rng, ifs, config);
+
+    // Precompute the truncated kernel
+    let sigma = config.splat_sigma;
+    let r = (3.0 * sigma).ceil() as i64;
+    let side = (2 * r + 1) as usize;
+    let kernel: Vec<f32> = (-r..=r)
+        .flat_map(|dy| {
+            (-r..=r)
+                .map(move |dx| (-((dx * dx + dy * dy) as f64) / (2.0 * sigma * sigma)).exp() as f32)
+        })
+        .collect();
+
+    // Accumulate the kernel around every point, clipped to the image
+    let mut buffer = vec![0.0f32; (width * height) as usize];
+    for (x, y) in points.xs.iter().zip(points.ys.iter()) {
+        let cx = x.trunc() as i64;
+        let cy = y.trunc() as i64;
+        for py in (cy - r).max(0)..=(cy + r).min(height - 1) {
+            let row = (py - cy + r) as usize * side;
+            for px in (cx - r).max(0)..=(cx + r).min(width - 1) {
+                buffer[(py * width + px) as usize] += kernel[row + (px - cx + r) as usize];
+            }
+        }
+    }
+
+    let max = buffer.iter().copied().fold(0.0f32, f32::max);
+    let intensities: Vec<f32> = if max > 0.0 {
+        buffer.iter().map(|&v| v.ln_1p() / max.ln_1p()).collect()
+    } else {
+        buffer
+    };
+    let color = random_julia_color(&mut rng);
+
+    Rendered {
+        image: intensity_image(&intensities, config, color),
+        bounds: points.bounds,
+        color: Some(color),
+        plotted: any_on_canvas(&points.xs, &points.ys, width as usize, height as usize),
+    }
+}
+
+/// Render a density image with 16 bits per channel
+///
+/// This is the same image as [`render_density`], but the blended channel
+/// values are quantized to 16 bits instead of 8, preserving smooth
+/// gradients for print or further processing. Dithering is not applied.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A 16-bit RGB image blending from the background color to a Julia color
+pub fn render_density_16<R: Rng + Clone>(
+    mut rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> ImageBuffer<Rgb<u16>, Vec<u16>> {
+    let counts = density_buffer(&mut rng, ifs, config);
+
+    let intensities = intensities(counts, config);
+    let color = random_julia_color(&mut rng);
+
+    // Scale 8-bit colors so that 255 maps to 65535
+    let data = blend_channels(&intensities, config.background, color)
+        .iter()
+        .map(|&v| (v * 257.0).round().clamp(0.0, 65_535.0) as u16)
+        .collect();
+    let mut image = ImageBuffer::from_raw(config.width as u32, config.height as u32, data).unwrap();
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Render the 16-bit density image of the IFS sampled from a seed
+///
+/// This renders an image already rendered by
+/// [`render_from_config`](crate::render_from_config) in the density mode
+/// again with 16 bits per channel, given the seed it was generated with (see
+/// [`RenderReport::seed`](crate::RenderReport::seed)). No retries are made,
+/// and `config.post_blur` is not applied.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+/// * `seed` - The seed the image was generated with
+///
+/// # Returns
+///
+/// A 16-bit RGB image blending from the background color to a Julia color
+pub fn render_density_16_from_seed(config: &Config, seed: u64) -> ImageBuffer<Rgb<u16>, Vec<u16>> {
+    let mut rng = seeded_rng(seed);
+    let ifs = ifs_from_config(&mut rng, config);
+
+    render_density_16(rng, &ifs, config)
+}
+
+/// Render a density image and save it with the bit depth of the configuration
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering; `bit_depth` selects between
+///   [`render_density`] and [`render_density_16`]
+/// * `path` - Output path; the format is chosen from its extension and must
+///   support the bit depth, e.g. PNG
+///
+/// # Returns
+///
+/// A Result containing () if successful, or an Error if not
+pub fn save_density<R: Rng + Clone, P: AsRef<Path>>(
+    rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+    path: P,
+) -> Result<()> {
+    match config.bit_depth {
+        8 => render_density(rng, ifs, config).save(path)?,
+        16 => render_density_16(rng, ifs, config).save(path)?,
+        depth => {
+            return Err(Error::ConfigError(format!(
+                "Unsupported bit depth: {}",
+                depth
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// Render a single-channel image of the orbit
+///
+/// Intensities follow `config.tone_map` (or equalization), mapped from black
+/// to white without any palette, so `ToneMap::Binary` gives a plain mask.
+/// A `GrayImage` takes a third of the memory of the equivalent `RgbImage`,
+/// which matters when generating large datasets.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A grayscale image of the attractor
+pub fn render_gray<R: Rng>(mut rng: R, ifs: &SigmaFactorIFS, config: &Config) -> GrayImage {
+    let counts = density_buffer(&mut rng, ifs, config);
+
+    let intensities = intensities(counts, config);
+    let values: Vec<f32> = intensities.iter().map(|&v| v * 255.0).collect();
+
+    let dither_seed = config.dither.then_some(config.seed);
+    let data = quantize(&values, dither_seed);
+    let mut image = ImageBuffer::from_raw(config.width as u32, config.height as u32, data).unwrap();
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Convert intensities to an image blending from the background to a color
+///
+/// # Arguments
+///
+/// * `intensities` - Per-pixel intensities in [0, 1]
+/// * `config` - Configuration for rendering
+/// * `color` - Color of a pixel with intensity 1
+///
+/// # Returns
+///
+/// An RGB image
+fn intensity_image(intensities: &[f32], config: &Config, color: Rgb<u8>) -> RgbImage {
+    let values = blend_channels(intensities, config.background, color);
+
+    let dither_seed = config.dither.then_some(config.seed);
+    let data = quantize(&values, dither_seed);
+    let mut image = ImageBuffer::from_raw(config.width as u32, config.height as u32, data).unwrap();
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Blend from a background to a color according to intensities
+///
+/// # Arguments
+///
+/// * `intensities` - Per-pixel intensities in [0, 1]
+/// * `background` - Color of a pixel with intensity 0
+/// * `color` - Color of a pixel with intensity 1
+///
+/// # Returns
+///
+/// Interleaved RGB channel values in [0, 255], before quantization
+fn blend_channels(intensities: &[f32], background: [u8; 3], color: Rgb<u8>) -> Vec<f32> {
+    let mut values = Vec::with_capacity(intensities.len() * 3);
+    for &v in intensities {
+        for c in 0..3 {
+            let bg = background[c] as f32;
+            let fg = color[c] as f32;
+            values.push(bg + (fg - bg) * v);
+        }
+    }
+
+    values
+}
+
+/// Quantize channel values in [0, 255] to 8 bits, optionally with dithering
+///
+/// With dithering enabled, seeded uniform noise in [-0.5, 0.5) is added to
+/// every value before rounding, which trades visible banding in smooth
+/// gradients for fine grain. The noise comes from an auxiliary stream of the
+/// seed, independent of the one that chose the geometry, so the output is
+/// reproducible for a given seed and every dithered value stays within 1 of
+/// the undithered one.
+///
+/// # Arguments
+///
+/// * `values` - Channel values in [0, 255]
+/// * `dither_seed` - Seed of the dithering noise, or `None` to round plainly
+///
+/// # Returns
+///
+/// The quantized channel values
+pub fn quantize(values: &[f32], dither_seed: Option<u64>) -> Vec<u8> {
+    match dither_seed {
+        None => values
+            .iter()
+            .map(|&v| v.round().clamp(0.0, 255.0) as u8)
+            .collect(),
+        Some(seed) => {
+            let mut rng = aux_rng(seed, DITHER_STREAM);
+            values
+                .iter()
+                .map(|&v| {
+                    let noise = rng.gen::<f32>() - 0.5;
+                    (v + noise).round().clamp(0.0, 255.0) as u8
+                })
+                .collect()
+        }
+    }
+}
+
+/// Save a float density buffer as a single-channel OpenEXR image
+///
+/// Unlike the 8- and 16-bit renderings, the values are stored as 32-bit
+/// floats in a channel named `Y`, so raw or tone-mapped densities keep
+/// their full dynamic range for HDR and machine learning pipelines. Hit
+/// counts from [`density_buffer`] can be converted with `as f32`.
+///
+/// # Arguments
+///
+/// * `buffer` - Row-major values of size `width * height`
+/// * `width` - Width of the image
+/// * `height` - Height of the image
+/// * `path` - Output path of the EXR file
+///
+/// # Returns
+///
+/// A Result containing () if successful, a ConfigError if the buffer does
+/// not match the size, or a RenderError if the file cannot be encoded or
+/// written
+#[cfg(feature = "exr")]
+pub fn save_density_exr<P: AsRef<Path>>(
+    buffer: &[f32],
+    width: usize,
+    height: usize,
+    path: P,
+) -> Result<()> {
+    use exr::prelude::{Image, SpecificChannels, Vec2, WritableImage};
+
+    if buffer.len() != width * height {
+        return Err(Error::ConfigError(format!(
+            "Density buffer has {} values, expected {}x{}",
+            buffer.len(),
+            width,
+            height
+        )));
+    }
+
+    let channels = SpecificChannels::build()
+        .with_channel("Y")
+        .with_pixel_fn(|Vec2(x, y)| (buffer[y * width + x],));
+    Image::from_channels((width, height), channels)
+        .write()
+        .to_file(path)
+        .map_err(|e| Error::RenderError(format!("Failed to encode EXR: {}", e)))
+}
@@ -0,0 +1,209 @@
+//! Export functions for the rust-random-logo library
+//!
+//! This module provides functions for writing rendered images and density
+//! buffers in simple formats that do not require an image encoder.
+
+use std::io::{self, Write};
+
+use image::RgbImage;
+
+use crate::core::config::Config;
+use crate::core::ifs::SigmaFactorIFS;
+use crate::core::renderer::{union_bounds, EMPTY_BOUNDS};
+use crate::core::types::Vector2f;
+use crate::core::utils::JULIA_COLORS;
+
+/// Write an RGB image as a binary (P6) PPM
+///
+/// # Arguments
+///
+/// * `img` - The image to write
+/// * `w` - The writer to write to
+///
+/// # Returns
+///
+/// An io::Result containing () if successful
+pub fn write_ppm<W: Write>(img: &RgbImage, w: &mut W) -> io::Result<()> {
+    write!(w, "P6\n{} {}\n255\n", img.width(), img.height())?;
+    w.write_all(img.as_raw())?;
+    Ok(())
+}
+
+/// Write a density buffer as a binary (P5) PGM
+///
+/// Counts are scaled linearly so that the largest count maps to 255.
+///
+/// # Arguments
+///
+/// * `density` - Row-major per-pixel hit counts
+/// * `width` - Width of the buffer
+/// * `height` - Height of the buffer
+/// * `w` - The writer to write to
+///
+/// # Returns
+///
+/// An io::Result containing () if successful, or an InvalidInput error if
+/// the buffer length does not match the dimensions
+pub fn write_pgm<W: Write>(
+    density: &[u32],
+    width: usize,
+    height: usize,
+    w: &mut W,
+) -> io::Result<()> {
+    if density.len() != width * height {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Density buffer has {} entries, expected {}x{}",
+                density.len(),
+                width,
+                height
+            ),
+        ));
+    }
+
+    let max = density.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let pixels: Vec<u8> = density
+        .iter()
+        .map(|&c| (255.0 * c as f64 / max).round() as u8)
+        .collect();
+
+    write!(w, "P5\n{} {}\n255\n", width, height)?;
+    w.write_all(&pixels)?;
+    Ok(())
+}
+
+/// Convert a point cloud to interleaved little-endian f64 bytes
+///
+/// The result can be loaded in Python with
+/// `numpy.frombuffer(data, dtype="<f8").reshape(-1, 2)`.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+///
+/// # Returns
+///
+/// The bytes `x0 y0 x1 y1 ...`, 16 bytes per point
+pub fn points_to_bytes(xs: &[f64], ys: &[f64]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(16 * xs.len().min(ys.len()));
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        bytes.extend_from_slice(&x.to_le_bytes());
+        bytes.extend_from_slice(&y.to_le_bytes());
+    }
+    bytes
+}
+
+/// Write a point cloud as CSV
+///
+/// The output starts with an `x,y` header row followed by one row per
+/// point. Coordinates are written in the shortest form that parses back to
+/// the same f64, so no precision is lost.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `w` - The writer to write to
+///
+/// # Returns
+///
+/// An io::Result containing () if successful
+pub fn write_points_csv<W: Write>(xs: &[f64], ys: &[f64], w: &mut W) -> io::Result<()> {
+    writeln!(w, "x,y")?;
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        writeln!(w, "{},{}", x, y)?;
+    }
+    Ok(())
+}
+
+/// Convert interleaved little-endian f64 bytes back to a point cloud
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes produced by [`points_to_bytes`]
+///
+/// # Returns
+///
+/// An io::Result containing the x and y coordinates, or an InvalidData error
+/// if the length is not a multiple of 16 bytes
+pub fn points_from_bytes(bytes: &[u8]) -> io::Result<(Vec<f64>, Vec<f64>)> {
+    if !bytes.len().is_multiple_of(16) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} bytes is not a whole number of points", bytes.len()),
+        ));
+    }
+
+    let n = bytes.len() / 16;
+    let mut xs = Vec::with_capacity(n);
+    let mut ys = Vec::with_capacity(n);
+    for chunk in bytes.chunks_exact(16) {
+        let (x, y) = chunk.split_at(8);
+        xs.push(f64::from_le_bytes(x.try_into().unwrap()));
+        ys.push(f64::from_le_bytes(y.try_into().unwrap()));
+    }
+
+    Ok((xs, ys))
+}
+
+/// Draw the first level of an IFS as an SVG wireframe
+///
+/// Every transformation maps the unit square to a parallelogram, and the
+/// parallelograms together show how the IFS tiles space. Each one is drawn
+/// as a `<polygon>` outline in its own color, cycling through the Julia
+/// colors. The `viewBox` is the bounding box of all parallelograms in IFS
+/// coordinates, with the y axis pointing down as usual in SVG, and the
+/// strokes are `config.stroke_width` pixels wide at any zoom.
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration providing the image size and stroke width
+///
+/// # Returns
+///
+/// The SVG document
+pub fn skeleton_to_svg(ifs: &SigmaFactorIFS, config: &Config) -> String {
+    let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    let polygons: Vec<Vec<Vector2f>> = ifs
+        .transforms()
+        .iter()
+        .map(|t| {
+            corners
+                .iter()
+                .map(|&(x, y)| t.apply(&Vector2f::new(x, y)))
+                .collect()
+        })
+        .collect();
+
+    let (x_min, x_max, y_min, y_max) = polygons.iter().flatten().fold(EMPTY_BOUNDS, |acc, p| {
+        union_bounds(acc, (p.x, p.x, p.y, p.y))
+    });
+    let (x_min, width, y_min, height) = if x_min <= x_max && y_min <= y_max {
+        (x_min, x_max - x_min, y_min, y_max - y_min)
+    } else {
+        (0.0, 1.0, 0.0, 1.0)
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">\n",
+        config.width, config.height, x_min, y_min, width, height
+    );
+    for (i, polygon) in polygons.iter().enumerate() {
+        let color = JULIA_COLORS[i % JULIA_COLORS.len()];
+        let points: Vec<String> = polygon.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+        svg.push_str(&format!(
+            "  <polygon points=\"{}\" fill=\"none\" stroke=\"rgb({},{},{})\" stroke-width=\"{}\" vector-effect=\"non-scaling-stroke\"/>\n",
+            points.join(" "),
+            color[0],
+            color[1],
+            color[2],
+            config.stroke_width
+        ));
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
@@ -7,10 +7,12 @@
 use nalgebra::{Matrix2, Rotation2, Vector2};
 use rand::distributions::{Distribution, WeightedIndex};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::core::affine::Affine;
 use crate::core::types::{Matrix2f, Vector2f, IFS};
 use crate::core::utils::uniform;
+use crate::error::{Error, Result};
 
 /// SigmaFactorIFS struct
 ///
@@ -18,10 +20,13 @@ use crate::core::utils::uniform;
 #[derive(Debug, Clone)]
 pub struct SigmaFactorIFS {
     /// The affine transformations
-    pub transforms: Vec<Affine>,
+    transforms: Vec<Affine>,
 
     /// The probability distribution for selecting transformations
-    pub weights: Vec<f64>,
+    weights: Vec<f64>,
+
+    /// Sampler over `weights`, validated and built once at construction
+    dist: WeightedIndex<f64>,
 }
 
 impl SigmaFactorIFS {
@@ -35,16 +40,245 @@ impl SigmaFactorIFS {
     /// # Returns
     ///
     /// A new SigmaFactorIFS
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arguments are rejected by [`SigmaFactorIFS::try_new`]
     pub fn new(transforms: Vec<Affine>, weights: Vec<f64>) -> Self {
-        assert_eq!(
-            transforms.len(),
-            weights.len(),
-            "Number of transforms must match number of weights"
-        );
-        Self {
+        Self::try_new(transforms, weights).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a new SigmaFactorIFS, validating its arguments
+    ///
+    /// # Arguments
+    ///
+    /// * `transforms` - The affine transformations
+    /// * `weights` - The probability weights for selecting transformations
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the SigmaFactorIFS, or a ConfigError if the
+    /// lengths differ, there are no transformations, or the weights are not
+    /// a valid distribution (negative, non-finite or all zero)
+    pub fn try_new(transforms: Vec<Affine>, weights: Vec<f64>) -> Result<Self> {
+        if transforms.len() != weights.len() {
+            return Err(Error::ConfigError(format!(
+                "Number of transforms ({}) must match number of weights ({})",
+                transforms.len(),
+                weights.len()
+            )));
+        }
+
+        if transforms.is_empty() {
+            return Err(Error::ConfigError(
+                "An IFS needs at least one transform".to_string(),
+            ));
+        }
+
+        let dist = WeightedIndex::new(&weights)
+            .map_err(|e| Error::ConfigError(format!("Invalid weights {:?}: {}", weights, e)))?;
+
+        Ok(Self {
             transforms,
             weights,
-        }
+            dist,
+        })
+    }
+}
+
+impl SigmaFactorIFS {
+    /// Get the affine transformations
+    ///
+    /// The transformations and weights are fixed at construction, so they
+    /// always agree with the sampler of `apply_random`; build a new IFS to
+    /// change them.
+    ///
+    /// # Returns
+    ///
+    /// The transformations, in storage order
+    pub fn transforms(&self) -> &[Affine] {
+        &self.transforms
+    }
+
+    /// Get the probability weights for selecting transformations
+    ///
+    /// # Returns
+    ///
+    /// The weights, in the order of [`transforms`](Self::transforms)
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Check whether every transformation of the IFS is a contraction
+    ///
+    /// # Returns
+    ///
+    /// `true` if all transformations are contractive
+    pub fn is_contractive(&self) -> bool {
+        self.transforms.iter().all(|t| t.is_contractive())
+    }
+
+    /// Compute the contraction ratio of every transformation
+    ///
+    /// The ratio of a transformation is the largest singular value of its
+    /// linear part, which bounds how much it can stretch any vector and so
+    /// correlates with the size of its sub-attractor.
+    ///
+    /// # Returns
+    ///
+    /// The contraction ratios, in the order of `transforms`
+    pub fn contraction_ratios(&self) -> Vec<f64> {
+        self.transforms
+            .iter()
+            .map(|t| t.contraction_ratio())
+            .collect()
+    }
+
+    /// Create a copy of the IFS keeping only some of its transformations
+    ///
+    /// The weights of the kept transformations are rescaled to sum to 1,
+    /// preserving their ratios.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - Whether to keep each transformation, in the order of
+    ///   `transforms`
+    ///
+    /// # Returns
+    ///
+    /// The masked IFS
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mask length differs from the number of transformations,
+    /// or if no transformation with a positive weight is kept
+    pub fn with_active_mask(&self, mask: &[bool]) -> SigmaFactorIFS {
+        assert_eq!(
+            mask.len(),
+            self.transforms.len(),
+            "Mask length must match the number of transforms"
+        );
+
+        let (transforms, weights): (Vec<Affine>, Vec<f64>) = self
+            .transforms
+            .iter()
+            .zip(self.weights.iter())
+            .zip(mask.iter())
+            .filter(|(_, &active)| active)
+            .map(|((t, &w), _)| (t.clone(), w))
+            .unzip();
+        let total: f64 = weights.iter().sum();
+        assert!(
+            total > 0.0,
+            "At least one weighted transform must remain active"
+        );
+
+        SigmaFactorIFS::new(transforms, weights.iter().map(|w| w / total).collect())
+    }
+
+    /// List the transformations from the most to the least likely
+    ///
+    /// The IFS itself is left in its storage order. Transformations with
+    /// equal weights keep their relative order.
+    ///
+    /// # Returns
+    ///
+    /// `(index, transform, weight)` triples sorted by descending weight,
+    /// where `index` is the position of the transformation in `transforms`
+    pub fn by_weight_desc(&self) -> Vec<(usize, &Affine, f64)> {
+        let mut sorted: Vec<(usize, &Affine, f64)> = self
+            .transforms
+            .iter()
+            .zip(self.weights.iter())
+            .enumerate()
+            .map(|(i, (t, &w))| (i, t, w))
+            .collect();
+        sorted.sort_by(|a, b| b.2.total_cmp(&a.2));
+        sorted
+    }
+
+    /// Apply a random transformation to a point and report which one was used
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - Random number generator
+    /// * `point` - The point to transform
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the index of the chosen transformation and the transformed point
+    pub fn apply_random_indexed<R: Rng>(&self, rng: &mut R, point: &Vector2f) -> (usize, Vector2f) {
+        let idx = self.dist.sample(rng);
+        (idx, self.transforms[idx].apply(point))
+    }
+}
+
+/// An IFS in the layout of the fractal pre-training reference implementation
+#[derive(Serialize, Deserialize)]
+struct PretrainingSystem {
+    /// One `[[a, b, e], [c, d, f]]` matrix `[W | b]` per transformation
+    system: Vec<[[f64; 3]; 2]>,
+
+    /// Selection probabilities; derived from the determinants if absent
+    #[serde(default)]
+    ps: Option<Vec<f64>>,
+}
+
+impl SigmaFactorIFS {
+    /// Serialize the IFS in the layout of the fractal pre-training reference
+    ///
+    /// The reference Python implementation stores an IFS as a NumPy array
+    /// `system` of shape `(n, 2, 3)`, where `system[i, :, :2]` is the linear
+    /// part and `system[i, :, 2]` the translation of map `i`, and passes the
+    /// selection probabilities separately as `ps`. The result is the JSON
+    /// object `{"system": [...], "ps": [...]}`, which `numpy.array` turns
+    /// back into the reference arrays. Both implementations apply
+    /// `x -> W x + b` to column vectors, so no coordinate change is needed.
+    ///
+    /// # Returns
+    ///
+    /// The JSON string
+    pub fn to_pretraining_json(&self) -> String {
+        let system = PretrainingSystem {
+            system: self
+                .transforms
+                .iter()
+                .map(|t| {
+                    let [a, b, c, d, e, f] = t.as_coefficients();
+                    [[a, b, e], [c, d, f]]
+                })
+                .collect(),
+            ps: Some(self.weights.clone()),
+        };
+
+        serde_json::to_string(&system).expect("An IFS always serializes to JSON")
+    }
+
+    /// Parse an IFS in the layout of the fractal pre-training reference
+    ///
+    /// This is the inverse of [`SigmaFactorIFS::to_pretraining_json`]. If
+    /// `ps` is missing, as for systems exported from the reference
+    /// parameter files, the probabilities are proportional to the absolute
+    /// determinants, which is also the default of the reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON object with the `system` and optional `ps` keys
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the SigmaFactorIFS, a JsonError if the JSON does
+    /// not have the expected layout, or a ConfigError if it does not describe
+    /// a valid IFS
+    pub fn from_pretraining_json(json: &str) -> Result<Self> {
+        let parsed: PretrainingSystem = serde_json::from_str(json)?;
+        let coefficients: Vec<[f64; 6]> = parsed
+            .system
+            .iter()
+            .map(|&[[a, b, e], [c, d, f]]| [a, b, c, d, e, f])
+            .collect();
+
+        ifs_from_coefficients(&coefficients, parsed.ps.as_deref())
     }
 }
 
@@ -53,9 +287,11 @@ impl IFS for SigmaFactorIFS {
     type Scalar = f64;
 
     fn apply_random<R: Rng>(&self, rng: &mut R, point: &Vector2f) -> Vector2f {
-        let dist = WeightedIndex::new(&self.weights).unwrap();
-        let idx = dist.sample(rng);
-        self.transforms[idx].apply(point)
+        self.apply_random_indexed(rng, point).1
+    }
+
+    fn num_transforms(&self) -> usize {
+        self.transforms.len()
     }
 }
 
@@ -146,6 +382,84 @@ pub fn rand_sigma_factor_ifs<R: Rng>(rng: &mut R) -> SigmaFactorIFS {
     // Number of transformations (2, 3, or 4)
     let n = rng.gen_range(2..=4);
 
+    rand_sigma_factor_ifs_with_count(rng, n)
+}
+
+/// Create a random SigmaFactorIFS with a given number of transformations
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `n` - Number of transformations, at least 2
+///
+/// # Returns
+///
+/// A random SigmaFactorIFS with `n` transformations
+///
+/// # Panics
+///
+/// Panics if `n` is less than 2; a single map has singular values close to
+/// 1, so it is not contractive
+pub fn rand_sigma_factor_ifs_with_count<R: Rng>(rng: &mut R, n: usize) -> SigmaFactorIFS {
+    assert!(
+        n >= 2,
+        "A random IFS needs at least two transformations, got {}",
+        n
+    );
+
+    sample_sigma_factor_ifs(rng, n, -1.0, 1.0)
+}
+
+/// Create a random SigmaFactorIFS with translations drawn from a given range
+///
+/// [`rand_sigma_factor_ifs`] draws both components of every translation
+/// uniformly from `[-1, 1]`; this draws them from `[lo, hi]` instead, so
+/// wider ranges spread the pieces of the attractor further apart and
+/// narrower ones make it more compact. With `lo = -1` and `hi = 1` the
+/// result is identical to [`rand_sigma_factor_ifs`] for the same random
+/// number generator.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `lo` - Lower bound of the translation components
+/// * `hi` - Upper bound of the translation components
+///
+/// # Returns
+///
+/// A Result containing the random SigmaFactorIFS, or a ConfigError if the
+/// bounds are not finite with `lo < hi`
+pub fn rand_sigma_factor_ifs_with_translation_range<R: Rng>(
+    rng: &mut R,
+    lo: f64,
+    hi: f64,
+) -> Result<SigmaFactorIFS> {
+    if !(lo < hi && lo.is_finite() && hi.is_finite()) {
+        return Err(Error::ConfigError(format!(
+            "Translation range must be finite with lo < hi, got [{}, {}]",
+            lo, hi
+        )));
+    }
+
+    // Number of transformations (2, 3, or 4)
+    let n = rng.gen_range(2..=4);
+
+    Ok(sample_sigma_factor_ifs(rng, n, lo, hi))
+}
+
+/// Sample a SigmaFactorIFS with `n` transformations
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `n` - Number of transformations, at least 2
+/// * `lo` - Lower bound of the translation components
+/// * `hi` - Upper bound of the translation components
+///
+/// # Returns
+///
+/// A random SigmaFactorIFS with `n` transformations
+fn sample_sigma_factor_ifs<R: Rng>(rng: &mut R, n: usize, lo: f64, hi: f64) -> SigmaFactorIFS {
     // Sigma factor bounds
     let alpha_lower = 0.5 * (5.0 + n as f64);
     let alpha_upper = 0.5 * (6.0 + n as f64);
@@ -171,8 +485,8 @@ pub fn rand_sigma_factor_ifs<R: Rng>(rng: &mut R) -> SigmaFactorIFS {
         let w = r_theta * sigma_mat * r_phi * d;
 
         // Create random translation vector
-        let b1 = uniform(rng, -1.0, 1.0);
-        let b2 = uniform(rng, -1.0, 1.0);
+        let b1 = uniform(rng, lo, hi);
+        let b2 = uniform(rng, lo, hi);
         let b = Vector2::new(b1, b2);
 
         // Create affine transformation
@@ -190,3 +504,64 @@ pub fn rand_sigma_factor_ifs<R: Rng>(rng: &mut R) -> SigmaFactorIFS {
 
     SigmaFactorIFS::new(transforms, weights)
 }
+
+/// Create a SigmaFactorIFS from explicit affine coefficients
+///
+/// Each transformation is given as `[a, b, c, d, e, f]`, the map
+/// `(x, y) -> (a * x + b * y + e, c * x + d * y + f)`.
+///
+/// # Arguments
+///
+/// * `coefficients` - The six coefficients of every transformation
+/// * `weights` - The probability weights for selecting transformations, or
+///   `None` to weight them by the absolute value of their determinants, as
+///   for a randomly generated IFS
+///
+/// # Returns
+///
+/// A Result containing the SigmaFactorIFS, or a ConfigError if the
+/// coefficients and weights do not describe a valid IFS
+pub fn ifs_from_coefficients(
+    coefficients: &[[f64; 6]],
+    weights: Option<&[f64]>,
+) -> Result<SigmaFactorIFS> {
+    let transforms: Vec<Affine> = coefficients
+        .iter()
+        .map(|&coefficients| Affine::from_coefficients(coefficients))
+        .collect();
+
+    let weights = match weights {
+        Some(weights) => weights.to_vec(),
+        None => transforms.iter().map(|t| t.determinant().abs()).collect(),
+    };
+
+    SigmaFactorIFS::try_new(transforms, weights)
+}
+
+/// Create a random SigmaFactorIFS whose transformations are all contractive
+///
+/// Calls [`rand_sigma_factor_ifs`] repeatedly until it produces an IFS whose
+/// transformations all have a largest singular value below 1.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `max_attempts` - Maximum number of IFS to generate before giving up
+///
+/// # Returns
+///
+/// A Result containing the first contractive SigmaFactorIFS, or an Error if
+/// none was found within `max_attempts`
+pub fn rand_contractive_ifs<R: Rng>(rng: &mut R, max_attempts: usize) -> Result<SigmaFactorIFS> {
+    for _ in 0..max_attempts {
+        let ifs = rand_sigma_factor_ifs(rng);
+        if ifs.is_contractive() {
+            return Ok(ifs);
+        }
+    }
+
+    Err(Error::IfsError(format!(
+        "No contractive IFS found after {} attempts",
+        max_attempts
+    )))
+}
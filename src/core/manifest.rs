@@ -0,0 +1,168 @@
+//! Dataset manifests for the rust-random-logo library
+//!
+//! This module provides a serializable record of how an image was generated,
+//! which makes datasets of rendered fractals self-describing and reproducible.
+
+use image::RgbImage;
+use serde::{Deserialize, Serialize};
+
+use crate::core::analysis::coverage;
+use crate::core::config::Config;
+use crate::core::ifs::SigmaFactorIFS;
+use crate::core::renderer::render_from_config_with_ifs;
+use crate::core::types::IFS;
+use crate::error::Result;
+
+/// Record of one transformation of an IFS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformRecord {
+    /// Rows of the linear transformation matrix
+    pub w: [[f64; 2]; 2],
+
+    /// The translation vector
+    pub b: [f64; 2],
+
+    /// Probability of selecting the transformation
+    pub weight: f64,
+
+    /// Largest singular value of `w`
+    pub contraction_ratio: f64,
+}
+
+/// Record of how an image was generated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Seed the image was generated with, which differs from `config.seed`
+    /// if degenerate attractors were retried
+    pub seed: u64,
+
+    /// Configuration used for rendering
+    pub config: Config,
+
+    /// Transformations of the IFS
+    pub transforms: Vec<TransformRecord>,
+
+    /// Fraction of pixels covered by the attractor
+    pub coverage: f64,
+}
+
+impl Manifest {
+    /// Build the manifest of an image rendered from an IFS
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed the image was generated with
+    /// * `config` - Configuration used for rendering
+    /// * `ifs` - The Iterated Function System
+    /// * `image` - The rendered image
+    ///
+    /// # Returns
+    ///
+    /// A new Manifest
+    pub fn new(seed: u64, config: &Config, ifs: &SigmaFactorIFS, image: &RgbImage) -> Self {
+        let transforms = ifs
+            .transforms()
+            .iter()
+            .zip(ifs.weights().iter())
+            .zip(ifs.contraction_ratios())
+            .map(|((t, &weight), contraction_ratio)| TransformRecord {
+                w: [[t.w.m11, t.w.m12], [t.w.m21, t.w.m22]],
+                b: [t.b.x, t.b.y],
+                weight,
+                contraction_ratio,
+            })
+            .collect();
+
+        Self {
+            seed,
+            config: config.clone(),
+            transforms,
+            coverage: coverage(image, config.background),
+        }
+    }
+
+    /// Serialize the manifest to pretty-printed JSON
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the JSON string if successful, or an Error if not
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Render an image from a configuration along with its manifest
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing the RGB image and its manifest if successful, or an
+/// Error if not
+pub fn generate_with_manifest(config: &Config) -> Result<(RgbImage, Manifest)> {
+    let (image, ifs, seed, _) = render_from_config_with_ifs(config)?;
+    let manifest = Manifest::new(seed, config, &ifs, &image);
+
+    Ok((image, manifest))
+}
+
+/// Summary of a rendering for provenance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderReport {
+    /// Seed the image was generated with, which differs from `config.seed`
+    /// if degenerate attractors were retried
+    pub seed: u64,
+
+    /// The random Julia color drawn for the image: the color of every point
+    /// with the `Single` scheme, the start color with `Temporal` and the
+    /// color of the first transformation with `WeightedTransform`. `None`
+    /// if no single random color shows in the image: for the skeleton and
+    /// distance modes and the `Orientation` and `AngularHue` schemes.
+    pub color: Option<[u8; 3]>,
+
+    /// Number of transformations of the IFS
+    pub num_transforms: usize,
+
+    /// Fraction of pixels covered by the attractor
+    pub coverage: f64,
+}
+
+impl RenderReport {
+    /// Serialize the report to pretty-printed JSON
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the JSON string if successful, or an Error if not
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Render an image from a configuration along with a report of its inputs
+///
+/// The chaos game runs once per attempt, as in
+/// [`render_from_config`](crate::render_from_config); the report is
+/// gathered from the render itself.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing the RGB image and its report if successful, or an
+/// Error if not
+pub fn render_reporting(config: &Config) -> Result<(RgbImage, RenderReport)> {
+    let (image, ifs, seed, color) = render_from_config_with_ifs(config)?;
+
+    let report = RenderReport {
+        seed,
+        color: color.map(|c| c.0),
+        num_transforms: ifs.num_transforms(),
+        coverage: coverage(&image, config.background),
+    };
+
+    Ok((image, report))
+}
@@ -2,15 +2,78 @@
 // This module contains the core components for generating fractal images
 
 pub mod affine;
+pub mod analysis;
+pub mod animation;
+pub mod basins;
+pub mod batch;
+pub mod cloud;
 pub mod config;
+pub mod density;
+pub mod export;
 pub mod ifs;
+pub mod manifest;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod postprocess;
 pub mod renderer;
 pub mod types;
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export commonly used items
 pub use affine::Affine;
-pub use config::Config;
-pub use ifs::{rand_sigma_factor_ifs, sample_svs, SigmaFactorIFS};
-pub use renderer::{generate_points, render};
+pub use analysis::{
+    attractor_stats, convex_hull, coverage, detect_symmetry, ifs_distance, is_degenerate,
+    luminance_histogram, similarity_dimension, AttractorStats, SymmetryReport, SYMMETRY_GRID_SIZE,
+    SYMMETRY_THRESHOLD,
+};
+pub use animation::{interpolate_ifs, render_frames};
+#[cfg(feature = "gif")]
+pub use animation::{render_gif, GIF_FRAME_DELAY_MS};
+pub use basins::render_basins;
+pub use batch::{
+    batch_output_path, find_seeds, render_batch_to_dir, render_grid, BatchSummary, SeedResult,
+};
+pub use cloud::PointCloud;
+pub use config::{
+    Config, DEFAULT_MAX_MEMORY_BYTES, MAX_ROTATIONAL_SYMMETRY, MAX_SUPERSAMPLE, RENDER_MODES,
+};
+#[cfg(feature = "exr")]
+pub use density::save_density_exr;
+pub use density::{
+    density_buffer, equalize, quantize, render_density, render_density_16,
+    render_density_16_from_seed, render_gray, render_splat, save_density, tone_map,
+    EQUALIZE_LEVELS,
+};
+pub use export::{
+    points_from_bytes, points_to_bytes, skeleton_to_svg, write_pgm, write_points_csv, write_ppm,
+};
+pub use ifs::{
+    ifs_from_coefficients, rand_contractive_ifs, rand_sigma_factor_ifs,
+    rand_sigma_factor_ifs_with_count, rand_sigma_factor_ifs_with_translation_range, sample_svs,
+    SigmaFactorIFS,
+};
+pub use manifest::{
+    generate_with_manifest, render_reporting, Manifest, RenderReport, TransformRecord,
+};
+#[cfg(feature = "parallel")]
+pub use parallel::render_parallel;
+pub use postprocess::{
+    apply_curves, blur, composite_over_gradient, distance_field, flip_horizontal, flip_vertical,
+    identity_curve, levels_curve, local_contrast, quantize_to_palette, resize, rotate_90, s_curve,
+    tile_preview,
+};
+pub use renderer::{
+    attractor_bounds, auto_point_size, check_extent, estimate_bounds, fold_orbit,
+    generate_clamped_points, generate_labeled_points, generate_points, generate_points_multistart,
+    normalize_points, point_bounds, points_from_config, points_from_seed, project_points, render,
+    render_deterministic, render_distance, render_glow, render_layers, render_multiseed,
+    render_overlay, render_rgba, render_skeleton, render_smoke, render_to_png_bytes, render_trails,
+    sample_attractor_point, try_render, FIXED_POINT_BITS, MAX_AUTO_POINT_SIZE,
+    MAX_DETERMINISTIC_POINTS, MULTISTART_WARMUP,
+};
 pub use types::*;
+pub use utils::{hsv_to_rgb, julia_gradient, rng_from_bytes, rng_state_to_bytes, seeded_rng};
+#[cfg(feature = "wasm")]
+pub use wasm::render_config_json;
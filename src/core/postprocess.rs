@@ -0,0 +1,537 @@
+//! Post-processing functions for the rust-random-logo library
+//!
+//! This module provides functions that operate on already rendered images,
+//! independently of the chaos game that produced them.
+
+use image::imageops::FilterType;
+use image::{imageops, GenericImage, Rgb, RgbImage, RgbaImage};
+
+use crate::core::config::Config;
+use crate::core::types::FilterKind;
+use crate::core::utils::lerp_color;
+
+/// Flip an image vertically in place
+///
+/// Image rows grow downward while mathematical y grows upward, so flipping
+/// vertically shows the attractor in the usual mathematical orientation.
+///
+/// # Arguments
+///
+/// * `img` - The image to flip
+pub fn flip_vertical(img: &mut RgbImage) {
+    imageops::flip_vertical_in_place(img);
+}
+
+/// Flip an image horizontally in place
+///
+/// # Arguments
+///
+/// * `img` - The image to flip
+pub fn flip_horizontal(img: &mut RgbImage) {
+    imageops::flip_horizontal_in_place(img);
+}
+
+/// Rotate an image by 90 degrees clockwise
+///
+/// # Arguments
+///
+/// * `img` - The image to rotate
+///
+/// # Returns
+///
+/// The rotated image, with width and height swapped
+pub fn rotate_90(img: &RgbImage) -> RgbImage {
+    imageops::rotate90(img)
+}
+
+/// Repeat an image in a grid to preview it as a seamless tile
+///
+/// Unlike [`render_grid`](crate::render_grid), which renders a different
+/// fractal per cell, every cell holds the same image, so the seams between
+/// copies show whether it works as a wallpaper or texture.
+///
+/// # Arguments
+///
+/// * `img` - The image to repeat
+/// * `rows` - Number of copies along the height
+/// * `cols` - Number of copies along the width
+///
+/// # Returns
+///
+/// An image of size `cols * width` by `rows * height`
+pub fn tile_preview(img: &RgbImage, rows: usize, cols: usize) -> RgbImage {
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+
+    let mut tiled = RgbImage::new((width * cols) as u32, (height * rows) as u32);
+    for row in 0..rows {
+        for col in 0..cols {
+            imageops::replace(&mut tiled, img, (col * width) as i64, (row * height) as i64);
+        }
+    }
+
+    tiled
+}
+
+/// Resample an image to a new size
+///
+/// # Arguments
+///
+/// * `img` - The image to resize
+/// * `width` - Width of the resized image
+/// * `height` - Height of the resized image
+/// * `filter` - The resampling filter
+///
+/// # Returns
+///
+/// The resized image, which is not required to keep the aspect ratio
+pub fn resize(img: &RgbImage, width: u32, height: u32, filter: FilterKind) -> RgbImage {
+    let filter = match filter {
+        FilterKind::Nearest => FilterType::Nearest,
+        FilterKind::Triangle => FilterType::Triangle,
+        FilterKind::CatmullRom => FilterType::CatmullRom,
+        FilterKind::Gaussian => FilterType::Gaussian,
+        FilterKind::Lanczos3 => FilterType::Lanczos3,
+    };
+    imageops::resize(img, width, height, filter)
+}
+
+/// Map every pixel of an image to its nearest color in a palette
+///
+/// Distances are Euclidean in RGB space; a pixel equidistant from several
+/// palette colors gets the one listed first. With dithering, the
+/// quantization error of every pixel is diffused to its unvisited neighbors
+/// with the Floyd–Steinberg weights (7/16 right, 3/16 below left, 5/16
+/// below, 1/16 below right), so flat regions keep their average color
+/// instead of banding. Dithering is deterministic.
+///
+/// # Arguments
+///
+/// * `img` - The image to quantize
+/// * `palette` - The allowed colors
+/// * `dither` - Whether to apply Floyd–Steinberg error diffusion
+///
+/// # Returns
+///
+/// The quantized image
+///
+/// # Panics
+///
+/// Panics if the palette is empty
+pub fn quantize_to_palette(img: &RgbImage, palette: &[Rgb<u8>], dither: bool) -> RgbImage {
+    assert!(
+        !palette.is_empty(),
+        "Palette must contain at least one color"
+    );
+
+    let nearest = |value: [f32; 3]| {
+        let distance = |color: &Rgb<u8>| {
+            (0..3)
+                .map(|c| {
+                    let d = value[c] - color[c] as f32;
+                    d * d
+                })
+                .sum::<f32>()
+        };
+        *palette
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            .unwrap()
+    };
+
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+    let mut values: Vec<[f32; 3]> = img
+        .pixels()
+        .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32])
+        .collect();
+
+    let mut result = RgbImage::new(img.width(), img.height());
+    for y in 0..height {
+        for x in 0..width {
+            let value = values[y * width + x];
+            let color = nearest(value);
+            result.put_pixel(x as u32, y as u32, color);
+
+            if !dither {
+                continue;
+            }
+
+            // Diffuse the error to the neighbors that are not quantized yet
+            let error: [f32; 3] = std::array::from_fn(|c| value[c] - color[c] as f32);
+            let neighbors = [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)];
+            for (dx, dy, weight) in neighbors {
+                let nx = x as i64 + dx;
+                let ny = y + dy;
+                if nx < 0 || nx >= width as i64 || ny >= height {
+                    continue;
+                }
+                let neighbor = &mut values[ny * width + nx as usize];
+                for c in 0..3 {
+                    neighbor[c] += error[c] * weight / 16.0;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Dim isolated pixels and brighten clustered ones of a plotted image
+///
+/// The most frequent color of the image is taken as the background. For
+/// every other pixel, the fraction `f` of occupied (non-background) pixels
+/// among its neighbors within `radius` (a square window, clipped to the
+/// image) scales its difference from the background by
+/// `1 + strength * (2 * f - 1)`. Pixels with half of their neighbors
+/// occupied are unchanged, isolated pixels fade toward the background and
+/// pixels inside dense clusters move away from it, clamped to [0, 255].
+///
+/// # Arguments
+///
+/// * `img` - The image to filter in place
+/// * `radius` - Half-width of the neighborhood in pixels
+/// * `strength` - Amount of the effect; 0 leaves the image unchanged and 1
+///   removes fully isolated pixels
+pub fn local_contrast(img: &mut RgbImage, radius: u32, strength: f64) {
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+    let r = radius as usize;
+
+    // The background is the most frequent color
+    let mut frequencies = std::collections::HashMap::new();
+    for pixel in img.pixels() {
+        *frequencies.entry(pixel.0).or_insert(0usize) += 1;
+    }
+    let Some((&background, _)) = frequencies.iter().max_by_key(|&(color, &n)| (n, *color)) else {
+        return;
+    };
+
+    // Summed-area table of occupied pixels, with a zero row and column
+    let mut sums = vec![0usize; (width + 1) * (height + 1)];
+    for y in 0..height {
+        for x in 0..width {
+            let occupied = (img.get_pixel(x as u32, y as u32).0 != background) as usize;
+            sums[(y + 1) * (width + 1) + x + 1] =
+                occupied + sums[y * (width + 1) + x + 1] + sums[(y + 1) * (width + 1) + x]
+                    - sums[y * (width + 1) + x];
+        }
+    }
+    let window = |x0: usize, y0: usize, x1: usize, y1: usize| {
+        sums[y1 * (width + 1) + x1] + sums[y0 * (width + 1) + x0]
+            - sums[y0 * (width + 1) + x1]
+            - sums[y1 * (width + 1) + x0]
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = img.get_pixel_mut(x as u32, y as u32);
+            if pixel.0 == background {
+                continue;
+            }
+
+            // Occupied neighbors, excluding the pixel itself
+            let (x0, y0) = (x.saturating_sub(r), y.saturating_sub(r));
+            let (x1, y1) = ((x + r + 1).min(width), (y + r + 1).min(height));
+            let neighbors = (x1 - x0) * (y1 - y0) - 1;
+            if neighbors == 0 {
+                continue;
+            }
+            let fraction = (window(x0, y0, x1, y1) - 1) as f64 / neighbors as f64;
+
+            let gain = (1.0 + strength * (2.0 * fraction - 1.0)).max(0.0);
+            for c in 0..3 {
+                let b = background[c] as f64;
+                pixel[c] = (b + (pixel[c] as f64 - b) * gain).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+}
+
+/// Composite a transparent image over a vertical gradient
+///
+/// The background blends linearly from `top` in the first row to `bottom`
+/// in the last row. The source has straight (non-premultiplied) alpha, as
+/// usual for RGBA images, and each pixel is premultiplied by its alpha
+/// before being added over the background, so the result is
+/// `src * alpha + background * (1 - alpha)` per channel.
+///
+/// # Arguments
+///
+/// * `fractal` - The image to composite
+/// * `top` - Background color of the first row
+/// * `bottom` - Background color of the last row
+///
+/// # Returns
+///
+/// An opaque RGB image of the same size
+pub fn composite_over_gradient(fractal: &RgbaImage, top: Rgb<u8>, bottom: Rgb<u8>) -> RgbImage {
+    let rows = fractal.height().saturating_sub(1).max(1) as f64;
+
+    RgbImage::from_fn(fractal.width(), fractal.height(), |x, y| {
+        let background = lerp_color(top, bottom, y as f64 / rows);
+        let src = fractal.get_pixel(x, y);
+        let alpha = src[3] as f64 / 255.0;
+        Rgb(std::array::from_fn(|c| {
+            let premultiplied = src[c] as f64 * alpha;
+            (premultiplied + background[c] as f64 * (1.0 - alpha)).round() as u8
+        }))
+    })
+}
+
+/// Map every channel of an image through its own lookup curve
+///
+/// # Arguments
+///
+/// * `img` - The image to grade in place
+/// * `r` - Output value of the red channel for every input value
+/// * `g` - Output value of the green channel for every input value
+/// * `b` - Output value of the blue channel for every input value
+pub fn apply_curves(img: &mut RgbImage, r: &[u8; 256], g: &[u8; 256], b: &[u8; 256]) {
+    for pixel in img.pixels_mut() {
+        pixel[0] = r[pixel[0] as usize];
+        pixel[1] = g[pixel[1] as usize];
+        pixel[2] = b[pixel[2] as usize];
+    }
+}
+
+/// Build the curve that leaves a channel unchanged
+///
+/// # Returns
+///
+/// The lookup curve mapping every value to itself
+pub fn identity_curve() -> [u8; 256] {
+    std::array::from_fn(|v| v as u8)
+}
+
+/// Build a contrast-raising S-curve
+///
+/// The curve blends the identity with the smoothstep `3x^2 - 2x^3` of the
+/// normalized value, which darkens shadows and brightens highlights while
+/// keeping black, mid-gray and white fixed.
+///
+/// # Arguments
+///
+/// * `strength` - Weight of the smoothstep, clamped to [0, 1]; 0 is the
+///   identity
+///
+/// # Returns
+///
+/// The lookup curve
+pub fn s_curve(strength: f64) -> [u8; 256] {
+    let strength = strength.clamp(0.0, 1.0);
+
+    std::array::from_fn(|v| {
+        let x = v as f64 / 255.0;
+        let smooth = x * x * (3.0 - 2.0 * x);
+        ((x + strength * (smooth - x)) * 255.0).round() as u8
+    })
+}
+
+/// Build a levels curve
+///
+/// Values up to `black` map to 0 and values from `white` map to 255. Values
+/// in between are rescaled to [0, 1], raised to the power `1 / gamma` and
+/// scaled back, so a gamma above 1 brightens the midtones.
+///
+/// # Arguments
+///
+/// * `black` - Input value mapped to black
+/// * `white` - Input value mapped to white
+/// * `gamma` - Midtone gamma; 1 keeps the rescaling linear
+///
+/// # Returns
+///
+/// The lookup curve
+///
+/// # Panics
+///
+/// Panics if `black` is not below `white` or `gamma` is not positive and
+/// finite
+pub fn levels_curve(black: u8, white: u8, gamma: f64) -> [u8; 256] {
+    assert!(
+        black < white,
+        "black must be below white, got {} and {}",
+        black,
+        white
+    );
+    assert!(
+        gamma > 0.0 && gamma.is_finite(),
+        "gamma must be positive and finite, got {}",
+        gamma
+    );
+
+    let range = (white - black) as f64;
+    std::array::from_fn(|v| {
+        let x = ((v as f64 - black as f64) / range).clamp(0.0, 1.0);
+        (x.powf(1.0 / gamma) * 255.0).round() as u8
+    })
+}
+
+/// Blur an image with a Gaussian kernel
+///
+/// The blur is separable: a horizontal and then a vertical pass with a
+/// normalized kernel truncated at 3 sigma. Pixels beyond the edges take the
+/// value of the nearest edge pixel.
+///
+/// # Arguments
+///
+/// * `img` - The image to blur in place
+/// * `sigma` - Standard deviation of the kernel in pixels; 0 or less leaves
+///   the image unchanged
+pub fn blur(img: &mut RgbImage, sigma: f64) {
+    if !(sigma > 0.0 && sigma.is_finite()) {
+        return;
+    }
+
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+    let radius = (3.0 * sigma).ceil() as i64;
+    let kernel: Vec<f64> = (-radius..=radius)
+        .map(|k| (-0.5 * (k as f64 / sigma).powi(2)).exp())
+        .collect();
+    let total: f64 = kernel.iter().sum();
+    let kernel: Vec<f64> = kernel.iter().map(|w| w / total).collect();
+
+    // Convolve along one axis, reading through the given pixel index
+    let convolve =
+        |src: &[f64], len: usize, count: usize, index: &dyn Fn(usize, usize) -> usize| {
+            let mut dst = vec![0.0; src.len()];
+            for line in 0..count {
+                for i in 0..len {
+                    let mut sum = [0.0; 3];
+                    for (k, w) in (-radius..=radius).zip(kernel.iter()) {
+                        let j = (i as i64 + k).clamp(0, len as i64 - 1) as usize;
+                        let offset = 3 * index(line, j);
+                        for c in 0..3 {
+                            sum[c] += w * src[offset + c];
+                        }
+                    }
+                    let offset = 3 * index(line, i);
+                    dst[offset..offset + 3].copy_from_slice(&sum);
+                }
+            }
+            dst
+        };
+
+    let buffer: Vec<f64> = img.as_raw().iter().map(|&v| v as f64).collect();
+    let buffer = convolve(&buffer, width, height, &|y, x| y * width + x);
+    let buffer = convolve(&buffer, height, width, &|x, y| y * width + x);
+
+    for (dst, src) in img.iter_mut().zip(buffer.iter()) {
+        *dst = src.round().clamp(0.0, 255.0) as u8;
+    }
+}
+
+/// Compute the distance from every pixel to the nearest drawn pixel
+///
+/// Every pixel that differs from the background counts as drawn. This is
+/// the exact Euclidean distance transform of the binary image, computed
+/// with the two-pass algorithm of Felzenszwalb and Huttenlocher: a pass of
+/// one-dimensional lower envelopes of parabolas along the columns, then
+/// along the rows, in linear time overall.
+///
+/// # Arguments
+///
+/// * `img` - The rendered image
+/// * `background` - Background color of the image
+///
+/// # Returns
+///
+/// The distances in pixels in row-major order, 0 at drawn pixels, or all
+/// infinite if nothing was drawn
+pub fn distance_field(img: &RgbImage, background: Rgb<u8>) -> Vec<f32> {
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+
+    // Squared distances, with drawn pixels as the zeros of the transform
+    let mut squared: Vec<f64> = img
+        .pixels()
+        .map(|&p| if p == background { f64::INFINITY } else { 0.0 })
+        .collect();
+
+    let mut column = vec![0.0; height];
+    for x in 0..width {
+        for y in 0..height {
+            column[y] = squared[y * width + x];
+        }
+        let transformed = squared_distance_1d(&column);
+        for y in 0..height {
+            squared[y * width + x] = transformed[y];
+        }
+    }
+    for row in squared.chunks_mut(width.max(1)) {
+        let transformed = squared_distance_1d(row);
+        row.copy_from_slice(&transformed);
+    }
+
+    squared.iter().map(|&d| d.sqrt() as f32).collect()
+}
+
+/// One-dimensional squared distance transform of a sampled function
+///
+/// Computes `min_q (p - q)^2 + f(q)` for every `p` as the lower envelope of
+/// the parabolas rooted at the finite samples of `f`.
+///
+/// # Arguments
+///
+/// * `f` - The sampled function, infinite where there is no parabola
+///
+/// # Returns
+///
+/// The transformed samples, all infinite if `f` has no finite sample
+fn squared_distance_1d(f: &[f64]) -> Vec<f64> {
+    // Roots of the parabolas on the lower envelope and where each starts
+    let mut roots: Vec<usize> = Vec::with_capacity(f.len());
+    let mut starts: Vec<f64> = Vec::with_capacity(f.len());
+    let intersection = |q: usize, r: usize| {
+        let (q2, r2) = ((q * q) as f64, (r * r) as f64);
+        ((f[q] + q2) - (f[r] + r2)) / (2.0 * (q as f64 - r as f64))
+    };
+
+    for q in (0..f.len()).filter(|&q| f[q].is_finite()) {
+        let mut s = f64::NEG_INFINITY;
+        while let Some(&r) = roots.last() {
+            s = intersection(q, r);
+            if s > *starts.last().unwrap() {
+                break;
+            }
+            roots.pop();
+            starts.pop();
+            s = f64::NEG_INFINITY;
+        }
+        roots.push(q);
+        starts.push(s);
+    }
+
+    if roots.is_empty() {
+        return vec![f64::INFINITY; f.len()];
+    }
+
+    let mut k = 0;
+    (0..f.len())
+        .map(|p| {
+            while k + 1 < roots.len() && starts[k + 1] < p as f64 {
+                k += 1;
+            }
+            let q = roots[k];
+            let d = p as f64 - q as f64;
+            d * d + f[q]
+        })
+        .collect()
+}
+
+/// Apply the flips requested by a configuration to a rendered image
+///
+/// # Arguments
+///
+/// * `img` - The image to orient
+/// * `config` - Configuration for rendering
+pub(crate) fn apply_orientation<I: GenericImage>(img: &mut I, config: &Config) {
+    if config.flip_x {
+        imageops::flip_horizontal_in_place(img);
+    }
+    if config.flip_y {
+        imageops::flip_vertical_in_place(img);
+    }
+}
@@ -3,13 +3,24 @@
 //! This module provides functions for generating points and rendering images
 //! based on Iterated Function Systems.
 
-use image::{ImageBuffer, RgbImage};
+use std::io::Cursor;
+
+use image::{ImageBuffer, ImageFormat, Rgb, RgbImage, RgbaImage};
+use rand::seq::SliceRandom;
 use rand::Rng;
 
+use crate::core::analysis::{attractor_stats, coverage, similarity_dimension};
 use crate::core::config::Config;
-use crate::core::ifs::SigmaFactorIFS;
-use crate::core::types::{Vector2f, IFS};
-use crate::core::utils::random_julia_color;
+use crate::core::density::{render_density_with_bounds, render_splat_with_bounds, tone_map};
+use crate::core::ifs::{
+    ifs_from_coefficients, rand_sigma_factor_ifs, rand_sigma_factor_ifs_with_count, SigmaFactorIFS,
+};
+use crate::core::postprocess::{apply_orientation, blur, distance_field};
+use crate::core::types::{ColorScheme, Projection, Symmetry, Vector2f, IFS};
+use crate::core::utils::{
+    aux_rng, hsv_to_rgb, julia_gradient, lerp_color, random_julia_color, random_julia_color_alpha,
+    seeded_rng, uniform, JULIA_COLORS, JULIA_GREEN, JULIA_PURPLE,
+};
 use crate::error::{Error, Result};
 
 /// Generate points using an Iterated Function System
@@ -31,6 +42,150 @@ pub fn generate_points<R: Rng + Clone>(
     n: usize,
     height: usize,
     width: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    // Generate points
+    let (mut xs, mut ys) = generate_raw_points(rng, ifs, n);
+
+    // Normalize points to fit within the output space
+    normalize_points(&mut xs, &mut ys, height, width);
+
+    (xs, ys)
+}
+
+/// Number of initial points of each orbit discarded by [`generate_points_multistart`]
+pub const MULTISTART_WARMUP: usize = 20;
+
+/// Generate points from several short orbits and normalize them together
+///
+/// Each orbit starts at a random point of `[-1, 1]^2` and discards its first
+/// [`MULTISTART_WARMUP`] points, which have not yet converged to the
+/// attractor. Compared to a single long orbit, this covers disconnected or
+/// weakly connected attractors more uniformly, at the cost of the warmup
+/// iterations of every start.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `n_per_start` - Number of points kept from each orbit
+/// * `n_starts` - Number of orbits
+/// * `height` - Height of the output space
+/// * `width` - Width of the output space
+///
+/// # Returns
+///
+/// A tuple of vectors containing the x and y coordinates of the merged
+/// orbits, in orbit order
+pub fn generate_points_multistart<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n_per_start: usize,
+    n_starts: usize,
+    height: usize,
+    width: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let mut xs = Vec::with_capacity(n_per_start * n_starts);
+    let mut ys = Vec::with_capacity(n_per_start * n_starts);
+
+    for _ in 0..n_starts {
+        let mut point = Vector2f::new(uniform(rng, -1.0, 1.0), uniform(rng, -1.0, 1.0));
+        for _ in 0..MULTISTART_WARMUP {
+            point = ifs.apply_random(rng, &point);
+        }
+
+        for _ in 0..n_per_start {
+            point = ifs.apply_random(rng, &point);
+            xs.push(point.x);
+            ys.push(point.y);
+        }
+    }
+
+    // Normalize the merged cloud
+    normalize_points(&mut xs, &mut ys, height, width);
+
+    (xs, ys)
+}
+
+/// Fold a closure over the points of an orbit without storing them
+///
+/// The chaos game is run from the origin for `n` steps, exactly as in
+/// [`generate_points`], and `f` is applied to every generated point in
+/// order. Memory use is constant in `n`, so custom statistics can be
+/// computed over orbits too long to materialize.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `n` - Number of points to generate
+/// * `init` - The initial accumulator
+/// * `f` - Combines the accumulator with the next point
+///
+/// # Returns
+///
+/// The final accumulator
+pub fn fold_orbit<R: Rng, B, F: FnMut(B, &Vector2f) -> B>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n: usize,
+    init: B,
+    mut f: F,
+) -> B {
+    let mut point = Vector2f::zeros();
+    let mut acc = init;
+    for _ in 0..n {
+        point = ifs.apply_random(rng, &point);
+        acc = f(acc, &point);
+    }
+
+    acc
+}
+
+/// Sample a single point of the attractor
+///
+/// The chaos game is run from the origin for `warmup` steps and the final
+/// point returned. The distance to the attractor shrinks by at least the
+/// largest contraction ratio at every step, so a few dozen steps place the
+/// point on the attractor to within floating-point precision for typical
+/// IFSs, distributed according to the invariant measure.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `warmup` - Number of chaos game steps
+///
+/// # Returns
+///
+/// The final point of the orbit
+pub fn sample_attractor_point<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    warmup: usize,
+) -> Vector2f {
+    let mut point = Vector2f::zeros();
+    for _ in 0..warmup {
+        point = ifs.apply_random(rng, &point);
+    }
+
+    point
+}
+
+/// Generate points using an Iterated Function System without normalizing them
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `n` - Number of points to generate
+///
+/// # Returns
+///
+/// A tuple of vectors containing the raw x and y coordinates of the orbit
+pub(crate) fn generate_raw_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n: usize,
 ) -> (Vec<f64>, Vec<f64>) {
     // Initialize point
     let mut point = Vector2f::zeros();
@@ -48,122 +203,2048 @@ pub fn generate_points<R: Rng + Clone>(
         ys.push(point.y);
     }
 
-    // Normalize points to fit within the output space
-    normalize_points(&mut xs, &mut ys, height, width);
-
     (xs, ys)
 }
 
-/// Normalize points to fit within the output space
+/// Generate points labeled with the transformation that produced them
 ///
 /// # Arguments
 ///
-/// * `xs` - X coordinates
-/// * `ys` - Y coordinates
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `n` - Number of points to generate
 /// * `height` - Height of the output space
 /// * `width` - Width of the output space
-fn normalize_points(xs: &mut [f64], ys: &mut [f64], height: usize, width: usize) {
-    // Find min and max values
-    let x_min = xs.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-    let x_max = xs.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-    let y_min = ys.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-    let y_max = ys.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-
-    // To prevent bounds errors when drawing points on a canvas,
-    // an offset value of 5 is used.
-    let offset = 5.0;
-    let width_range = (width as f64 - offset) - offset;
-    let height_range = (height as f64 - offset) - offset;
+///
+/// # Returns
+///
+/// A vector of normalized `(x, y, transform_index)` triples
+pub fn generate_labeled_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n: usize,
+    height: usize,
+    width: usize,
+) -> Vec<(f64, f64, usize)> {
+    let (mut xs, mut ys, labels) = generate_raw_labeled_points(rng, ifs, n);
+    normalize_points(&mut xs, &mut ys, height, width);
 
-    // Normalize points
-    for x in xs.iter_mut() {
-        *x = width_range * (*x - x_min) / (x_max - x_min) + offset;
-    }
+    xs.into_iter()
+        .zip(ys)
+        .zip(labels)
+        .map(|((x, y), label)| (x, y, label))
+        .collect()
+}
 
-    for y in ys.iter_mut() {
-        *y = height_range * (*y - y_min) / (y_max - y_min) + offset;
-    }
+/// Generate points and the index of the transformation that produced each one
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `n` - Number of points to generate
+///
+/// # Returns
+///
+/// A tuple of vectors containing the raw x and y coordinates of the orbit and
+/// the transformation index of each point
+pub(crate) fn generate_raw_labeled_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n: usize,
+) -> (Vec<f64>, Vec<f64>, Vec<usize>) {
+    let (xs, ys, labels, _) = generate_raw_clamped_points(rng, ifs, n, None);
+    (xs, ys, labels)
 }
 
-/// Render an image using an Iterated Function System
+/// Generate labeled points, resetting the orbit whenever it leaves a disk
+///
+/// A point whose distance from the origin exceeds `clamp_radius` (or is not
+/// finite) is replaced by the previous point of the orbit, which keeps
+/// borderline-contractive IFSs from running away without aborting.
 ///
 /// # Arguments
 ///
 /// * `rng` - Random number generator
 /// * `ifs` - The Iterated Function System
-/// * `config` - Configuration for rendering
+/// * `n` - Number of points to generate
+/// * `clamp_radius` - Radius of the disk, or `None` to never reset
 ///
 /// # Returns
 ///
-/// An RGB image
-pub fn render<R: Rng + Clone>(mut rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
-    let height = config.height;
-    let width = config.width;
-    let npoints = config.npoints;
+/// A tuple of the raw x and y coordinates, the transformation index of each
+/// point and the number of resets
+pub(crate) fn generate_raw_clamped_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n: usize,
+    clamp_radius: Option<f64>,
+) -> (Vec<f64>, Vec<f64>, Vec<usize>, usize) {
+    // Initialize point
+    let mut point = Vector2f::zeros();
+    let mut clamps = 0;
 
     // Generate points
-    let (xs, ys) = generate_points(&mut rng, ifs, npoints, height, width);
-
-    // Create image
-    let mut image = ImageBuffer::new(width as u32, height as u32);
+    let mut xs = Vec::with_capacity(n);
+    let mut ys = Vec::with_capacity(n);
+    let mut labels = Vec::with_capacity(n);
 
-    // Draw points
-    let color = random_julia_color(&mut rng);
-    for (x, y) in xs.iter().zip(ys.iter()) {
-        let x = x.trunc() as u32;
-        let y = y.trunc() as u32;
+    for _ in 0..n {
+        // Apply a random transformation
+        let (idx, next) = ifs.apply_random_indexed(rng, &point);
 
-        // Check bounds
-        if x < width as u32 && y < height as u32 {
-            image.put_pixel(x, y, color);
+        // Stay at the previous point if the orbit left the disk
+        match clamp_radius {
+            Some(radius) if next.norm() > radius || next.norm().is_nan() => clamps += 1,
+            _ => point = next,
         }
+
+        // Store the point
+        xs.push(point.x);
+        ys.push(point.y);
+        labels.push(idx);
     }
 
-    image
+    (xs, ys, labels, clamps)
 }
 
-/// Render an image using a configuration file
+/// Generate normalized points, resetting the orbit whenever it leaves a disk
 ///
 /// # Arguments
 ///
-/// * `config` - Configuration for rendering
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `n` - Number of points to generate
+/// * `height` - Height of the output space
+/// * `width` - Width of the output space
+/// * `clamp_radius` - Radius of the disk around the origin the orbit is
+///   confined to, or `None` to never reset
 ///
 /// # Returns
 ///
-/// A Result containing an RGB image if successful, or an Error if not
-pub fn render_from_config(config: &Config) -> Result<RgbImage> {
-    use rand::SeedableRng;
-    use rand_xoshiro::Xoshiro256PlusPlus;
-
-    // Validate IFS configuration
-    if config.ifs_name != "SigmaFactorIFS" {
-        return Err(Error::ConfigError(format!(
-            "Unknown IFS: {}",
-            config.ifs_name
-        )));
+/// A tuple of the normalized x and y coordinates and the number of points
+/// that were reset to the previous point of the orbit
+pub fn generate_clamped_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    n: usize,
+    height: usize,
+    width: usize,
+    clamp_radius: Option<f64>,
+) -> (Vec<f64>, Vec<f64>, usize) {
+    let (mut xs, mut ys, _, clamps) = generate_raw_clamped_points(rng, ifs, n, clamp_radius);
+    normalize_points(&mut xs, &mut ys, height, width);
+
+    (xs, ys, clamps)
+}
+
+/// Compute the bounding box of the attractor without rendering it
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `npoints` - Number of points to generate
+///
+/// # Returns
+///
+/// The raw bounding box `(x_min, x_max, y_min, y_max)` of the orbit
+pub fn attractor_bounds<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    npoints: usize,
+) -> (f64, f64, f64, f64) {
+    let (xs, ys) = generate_raw_points(rng, ifs, npoints);
+    point_bounds(&xs, &ys)
+}
+
+/// Estimate the bounding box of the attractor from the start of its orbit
+///
+/// Only the first `sample` points of the orbit are generated, and their
+/// bounding box is widened by `margin_frac` of its extent on every side, so
+/// a renderer can fix its bounds before streaming the rest of the orbit.
+/// This is an estimate: rarely visited parts of the attractor may first be
+/// reached after the sample, and points outside the box are clipped. A
+/// larger sample or margin lowers that risk, at the cost of time or of
+/// unused space around the attractor; a margin of 0.05 to 0.1 usually
+/// covers a sample of a few thousand points.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `sample` - Number of points to generate
+/// * `margin_frac` - Fraction of the extent added on every side
+///
+/// # Returns
+///
+/// The estimated bounding box `(x_min, x_max, y_min, y_max)`, empty (with
+/// infinite minima and maxima of the wrong sign) if `sample` is 0
+///
+/// # Panics
+///
+/// Panics if `margin_frac` is negative or not finite
+pub fn estimate_bounds<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    sample: usize,
+    margin_frac: f64,
+) -> (f64, f64, f64, f64) {
+    assert!(
+        margin_frac >= 0.0 && margin_frac.is_finite(),
+        "margin_frac must be non-negative and finite, got {}",
+        margin_frac
+    );
+
+    let (x_min, x_max, y_min, y_max) = attractor_bounds(rng, ifs, sample);
+    if sample == 0 {
+        return EMPTY_BOUNDS;
     }
+    let dx = margin_frac * (x_max - x_min);
+    let dy = margin_frac * (y_max - y_min);
 
-    if config.ndims != 2 {
-        return Err(Error::ConfigError(format!(
-            "Unsupported dimension: {}",
-            config.ndims
-        )));
+    (x_min - dx, x_max + dx, y_min - dy, y_max + dy)
+}
+
+/// Compute the bounding box of a set of points
+///
+/// The four extrema are computed in a single pass over the points.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates, as many as `xs`
+///
+/// # Returns
+///
+/// The bounding box `(x_min, x_max, y_min, y_max)`
+pub fn point_bounds(xs: &[f64], ys: &[f64]) -> (f64, f64, f64, f64) {
+    debug_assert_eq!(xs.len(), ys.len());
+
+    xs.iter()
+        .zip(ys.iter())
+        .fold(EMPTY_BOUNDS, |(x_min, x_max, y_min, y_max), (&x, &y)| {
+            (x_min.min(x), x_max.max(x), y_min.min(y), y_max.max(y))
+        })
+}
+
+/// Compute the smallest bounding box containing two bounding boxes
+///
+/// # Arguments
+///
+/// * `a` - The first bounding box `(x_min, x_max, y_min, y_max)`
+/// * `b` - The second bounding box `(x_min, x_max, y_min, y_max)`
+///
+/// # Returns
+///
+/// The union of both bounding boxes
+pub(crate) fn union_bounds(
+    a: (f64, f64, f64, f64),
+    b: (f64, f64, f64, f64),
+) -> (f64, f64, f64, f64) {
+    (a.0.min(b.0), a.1.max(b.1), a.2.min(b.2), a.3.max(b.3))
+}
+
+/// The empty bounding box, which is the identity of [`union_bounds`]
+pub(crate) const EMPTY_BOUNDS: (f64, f64, f64, f64) = (
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+);
+
+/// Apply a projection to raw points in place
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `projection` - The projection to apply
+pub fn project_points(xs: &mut Vec<f64>, ys: &mut Vec<f64>, projection: Projection) {
+    match projection {
+        Projection::Cartesian => {}
+        Projection::LogPolar => {
+            // Skip the origin, where ln|z| is undefined
+            let (pxs, pys) = xs
+                .iter()
+                .zip(ys.iter())
+                .filter(|(&x, &y)| x != 0.0 || y != 0.0)
+                .map(|(&x, &y)| (x.hypot(y).ln(), y.atan2(x)))
+                .unzip();
+            *xs = pxs;
+            *ys = pys;
+        }
     }
+}
 
-    // Create RNG
-    let mut rng = match config.rng_name.as_str() {
-        "Xoshiro256PlusPlus" => Xoshiro256PlusPlus::seed_from_u64(config.seed),
-        _ => {
-            return Err(Error::ConfigError(format!(
-                "Unknown RNG: {}",
-                config.rng_name
-            )))
+/// Keep only the points satisfying a predicate, keeping labels aligned
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `labels` - Per-point labels
+/// * `keep` - Predicate on `(x, y)` deciding whether a point is kept
+pub(crate) fn retain_points<F: Fn(f64, f64) -> bool>(
+    xs: &mut Vec<f64>,
+    ys: &mut Vec<f64>,
+    labels: &mut Vec<usize>,
+    keep: F,
+) {
+    let mut write = 0;
+    for read in 0..xs.len() {
+        if keep(xs[read], ys[read]) {
+            xs[write] = xs[read];
+            ys[write] = ys[read];
+            labels[write] = labels[read];
+            write += 1;
         }
-    };
+    }
+    xs.truncate(write);
+    ys.truncate(write);
+    labels.truncate(write);
+}
+
+/// Points of an orbit ready to be rasterized
+pub(crate) struct PreparedPoints {
+    /// X coordinates in pixels
+    pub xs: Vec<f64>,
 
-    // Create IFS
-    let ifs = crate::core::ifs::rand_sigma_factor_ifs(&mut rng);
+    /// Y coordinates in pixels
+    pub ys: Vec<f64>,
+
+    /// Index of the transformation that produced each point
+    pub labels: Vec<usize>,
+
+    /// Bounding box `(x_min, x_max, y_min, y_max)` mapped to the canvas
+    pub bounds: (f64, f64, f64, f64),
+}
+
+/// Generate an orbit and map it to pixel coordinates according to a configuration
+///
+/// This runs the steps shared by all renderers: the chaos game, the
+/// projection, the symmetry, the normalization to the canvas and the region
+/// of interest.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The prepared points
+pub(crate) fn prepare_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> PreparedPoints {
+    let (mut xs, mut ys, mut labels) = orbit_points(rng, ifs, config);
+    let bounds = frame_points(&mut xs, &mut ys, &mut labels, config);
+    place_points(&mut xs, &mut ys, &mut labels, bounds, config, config.seed);
+
+    PreparedPoints {
+        xs,
+        ys,
+        labels,
+        bounds,
+    }
+}
+
+/// Generate an orbit in the plane according to a configuration
+///
+/// This is the first stage of [`prepare_points`]: the chaos game, confined
+/// to `config.clamp_radius`, the projection and the symmetry.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The x and y coordinates of the points and the index of the
+/// transformation that produced each point
+fn orbit_points<R: Rng>(
+    rng: &mut R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> (Vec<f64>, Vec<f64>, Vec<usize>) {
+    // Generate points
+    let (mut xs, mut ys, mut labels, _) =
+        generate_raw_clamped_points(rng, ifs, config.npoints, config.clamp_radius);
+
+    // Project points, skipping those the projection is undefined for
+    if config.projection == Projection::LogPolar {
+        retain_points(&mut xs, &mut ys, &mut labels, |x, y| x != 0.0 || y != 0.0);
+    }
+    project_points(&mut xs, &mut ys, config.projection);
+
+    // Add the symmetric images of the points before normalizing, so the
+    // whole kaleidoscope fits the canvas
+    if let Some(symmetry) = config.symmetry {
+        symmetrize_points(&mut xs, &mut ys, &mut labels, symmetry);
+    }
+
+    (xs, ys, labels)
+}
+
+/// Find the window of the plane mapped to the canvas
+///
+/// Points outside `config.fixed_bounds`, if set, are clipped.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `labels` - Per-point labels
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The bounding box `(x_min, x_max, y_min, y_max)`: the fixed bounds, or
+/// else the bounding box of the points
+fn frame_points(
+    xs: &mut Vec<f64>,
+    ys: &mut Vec<f64>,
+    labels: &mut Vec<usize>,
+    config: &Config,
+) -> (f64, f64, f64, f64) {
+    match config.fixed_bounds {
+        Some(bounds) => {
+            let (x_min, x_max, y_min, y_max) = bounds;
+            retain_points(xs, ys, labels, |x, y| {
+                (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y)
+            });
+            bounds
+        }
+        None => point_bounds(xs, ys),
+    }
+}
+
+/// Map points to pixel coordinates
+///
+/// The bounding box is mapped to the canvas, then the region of interest is
+/// zoomed into and the points are jittered, as configured.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `labels` - Per-point labels
+/// * `bounds` - The bounding box `(x_min, x_max, y_min, y_max)` to map to
+///   the canvas
+/// * `config` - Configuration for rendering
+/// * `seed` - Seed of the jitter noise
+fn place_points(
+    xs: &mut Vec<f64>,
+    ys: &mut Vec<f64>,
+    labels: &mut Vec<usize>,
+    bounds: (f64, f64, f64, f64),
+    config: &Config,
+    seed: u64,
+) {
+    let height = config.height;
+    let width = config.width;
+
+    normalize_points_to_bounds(xs, ys, bounds, height, width);
+
+    // Zoom into the region of interest
+    if let Some(roi) = config.roi {
+        apply_roi(xs, ys, labels, roi, height, width);
+    }
+
+    // Perturb points within their pixel neighborhood to break up aliasing
+    if config.jitter {
+        jitter_points(xs, ys, seed);
+    }
+}
+
+/// Append the symmetric images of every point about the centroid
+///
+/// The images are appended after the original points, one copy at a time,
+/// so the first `n` points remain the orbit in iteration order.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `labels` - Per-point labels, copied to the images
+/// * `symmetry` - The symmetry to impose
+fn symmetrize_points(
+    xs: &mut Vec<f64>,
+    ys: &mut Vec<f64>,
+    labels: &mut Vec<usize>,
+    symmetry: Symmetry,
+) {
+    let n = xs.len();
+    let (cx, cy) = attractor_stats(xs, ys).centroid;
+    let maps: Vec<(f64, f64, f64, f64)> = match symmetry {
+        Symmetry::MirrorX => vec![(-1.0, 0.0, 0.0, 1.0)],
+        Symmetry::MirrorY => vec![(1.0, 0.0, 0.0, -1.0)],
+        Symmetry::Rotational(k) => (1..k)
+            .map(|i| {
+                let (sin, cos) = (std::f64::consts::TAU * i as f64 / k as f64).sin_cos();
+                (cos, -sin, sin, cos)
+            })
+            .collect(),
+    };
+
+    for (a, b, c, d) in maps {
+        for i in 0..n {
+            let (x, y) = (xs[i] - cx, ys[i] - cy);
+            xs.push(a * x + b * y + cx);
+            ys.push(c * x + d * y + cy);
+            labels.push(labels[i]);
+        }
+    }
+}
+
+/// Auxiliary random stream of the jitter noise
+const JITTER_STREAM: u32 = 1;
+
+/// Auxiliary random stream of the shuffled draw order
+const SHUFFLE_STREAM: u32 = 2;
+
+/// Auxiliary random stream of the dithering noise
+pub(crate) const DITHER_STREAM: u32 = 3;
+
+/// Perturb every point by up to half a pixel along each axis
+///
+/// The noise comes from an auxiliary stream of `seed`, so it is reproducible
+/// and does not overlap with the stream that chose the geometry, nor shift
+/// what that stream produces.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates in pixels
+/// * `ys` - Y coordinates in pixels
+/// * `seed` - Seed of the jitter noise
+fn jitter_points(xs: &mut [f64], ys: &mut [f64], seed: u64) {
+    let mut rng = aux_rng(seed, JITTER_STREAM);
+    for (x, y) in xs.iter_mut().zip(ys.iter_mut()) {
+        *x += rng.gen::<f64>() - 0.5;
+        *y += rng.gen::<f64>() - 0.5;
+    }
+}
+
+/// Rescale a region of interest of the canvas to fill it, dropping other points
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates in pixels
+/// * `ys` - Y coordinates in pixels
+/// * `labels` - Per-point labels
+/// * `roi` - The region `(x0, x1, y0, y1)` in fractions of the canvas size
+/// * `height` - Height of the output space
+/// * `width` - Width of the output space
+pub(crate) fn apply_roi(
+    xs: &mut Vec<f64>,
+    ys: &mut Vec<f64>,
+    labels: &mut Vec<usize>,
+    roi: (f64, f64, f64, f64),
+    height: usize,
+    width: usize,
+) {
+    let (x0, x1, y0, y1) = roi;
+    let w = width as f64;
+    let h = height as f64;
+
+    retain_points(xs, ys, labels, |x, y| {
+        let u = x / w;
+        let v = y / h;
+        (x0..=x1).contains(&u) && (y0..=y1).contains(&v)
+    });
+
+    for x in xs.iter_mut() {
+        *x = (*x / w - x0) / (x1 - x0) * w;
+    }
+    for y in ys.iter_mut() {
+        *y = (*y / h - y0) / (y1 - y0) * h;
+    }
+}
+
+/// Normalize points to fit within the output space
+///
+/// The bounding box of the points is mapped to the canvas, keeping a margin
+/// of 5 pixels on every side.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `height` - Height of the output space
+/// * `width` - Width of the output space
+pub fn normalize_points(xs: &mut [f64], ys: &mut [f64], height: usize, width: usize) {
+    // Find min and max values
+    let bounds = point_bounds(xs, ys);
+
+    normalize_points_to_bounds(xs, ys, bounds, height, width);
+}
+
+/// Normalize points against a given bounding box
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates
+/// * `ys` - Y coordinates
+/// * `bounds` - The bounding box `(x_min, x_max, y_min, y_max)` mapped to the canvas
+/// * `height` - Height of the output space
+/// * `width` - Width of the output space
+pub(crate) fn normalize_points_to_bounds(
+    xs: &mut [f64],
+    ys: &mut [f64],
+    bounds: (f64, f64, f64, f64),
+    height: usize,
+    width: usize,
+) {
+    let (x_min, x_max, y_min, y_max) = bounds;
+
+    // To prevent bounds errors when drawing points on a canvas,
+    // an offset value of 5 is used.
+    let offset = 5.0;
+    let width_range = (width as f64 - offset) - offset;
+    let height_range = (height as f64 - offset) - offset;
+
+    // Normalize points, centering an axis with zero extent instead of
+    // dividing by zero
+    let x_extent = x_max - x_min;
+    for x in xs.iter_mut() {
+        *x = if x_extent > 0.0 {
+            width_range * (*x - x_min) / x_extent + offset
+        } else {
+            0.5 * width_range + offset
+        };
+    }
+
+    let y_extent = y_max - y_min;
+    for y in ys.iter_mut() {
+        *y = if y_extent > 0.0 {
+            height_range * (*y - y_min) / y_extent + offset
+        } else {
+            0.5 * height_range + offset
+        };
+    }
+}
+
+/// Check that a bounding box has a positive, finite extent along both axes
+///
+/// # Arguments
+///
+/// * `bounds` - The bounding box `(x_min, x_max, y_min, y_max)`
+///
+/// # Returns
+///
+/// A Result containing () if the bounding box is usable for normalization,
+/// or an Error if the attractor collapsed or diverged
+pub fn check_extent(bounds: (f64, f64, f64, f64)) -> Result<()> {
+    let (x_min, x_max, y_min, y_max) = bounds;
+    let x_extent = x_max - x_min;
+    let y_extent = y_max - y_min;
+
+    if !(x_extent > 0.0 && y_extent > 0.0) {
+        return Err(Error::DegenerateError(format!(
+            "Attractor has zero extent ({} x {}); try increasing npoints",
+            x_extent, y_extent
+        )));
+    }
+
+    if !(x_extent.is_finite() && y_extent.is_finite()) {
+        return Err(Error::DegenerateError(
+            "Attractor has infinite extent; the orbit diverged".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Plot a point as a filled square, clipped to the image
+///
+/// # Arguments
+///
+/// * `image` - The image to draw into
+/// * `x` - X coordinate of the point in pixels
+/// * `y` - Y coordinate of the point in pixels
+/// * `color` - Color of the point
+/// * `radius` - Half-width of the square in pixels; 0 plots a single pixel
+pub(crate) fn plot_point(image: &mut RgbImage, x: f64, y: f64, color: Rgb<u8>, radius: u32) {
+    let width = image.width() as i64;
+    let height = image.height() as i64;
+    let cx = x.trunc() as i64;
+    let cy = y.trunc() as i64;
+    let r = radius as i64;
+
+    for py in (cy - r).max(0)..=(cy + r).min(height - 1) {
+        for px in (cx - r).max(0)..=(cx + r).min(width - 1) {
+            image.put_pixel(px as u32, py as u32, color);
+        }
+    }
+}
+
+/// Largest point size considered by [`auto_point_size`]
+pub const MAX_AUTO_POINT_SIZE: u32 = 16;
+
+/// Pick the point size whose binary rendering covers a target fraction of the canvas
+///
+/// The hit mask of the points is dilated one pixel at a time, which is
+/// equivalent to growing the plotted squares, until the covered fraction
+/// reaches the target or [`MAX_AUTO_POINT_SIZE`] is hit. The size whose
+/// coverage is closest to the target is returned, so faint attractors get
+/// thicker points while ones that already fill the canvas stay thin.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates of the points in pixels
+/// * `ys` - Y coordinates of the points in pixels
+/// * `width` - Width of the canvas
+/// * `height` - Height of the canvas
+/// * `target` - Target fraction of covered pixels
+///
+/// # Returns
+///
+/// The point size, usable as `Config::point_size`
+pub fn auto_point_size(xs: &[f64], ys: &[f64], width: usize, height: usize, target: f64) -> u32 {
+    let total = (width * height) as f64;
+    if total == 0.0 {
+        return 0;
+    }
+
+    let mut mask = vec![false; width * height];
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        let x = x.trunc() as usize;
+        let y = y.trunc() as usize;
+        if x < width && y < height {
+            mask[y * width + x] = true;
+        }
+    }
+
+    let coverage = |mask: &[bool]| mask.iter().filter(|&&m| m).count() as f64 / total;
+    let mut best = (0, (coverage(&mask) - target).abs());
+    let mut current = coverage(&mask);
+
+    for size in 1..=MAX_AUTO_POINT_SIZE {
+        if current >= target {
+            break;
+        }
+
+        // Grow every covered pixel by one pixel in each direction
+        let mut grown = mask.clone();
+        for y in 0..height {
+            for x in 0..width {
+                if mask[y * width + x] {
+                    for py in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                        for px in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                            grown[py * width + px] = true;
+                        }
+                    }
+                }
+            }
+        }
+        mask = grown;
+
+        current = coverage(&mask);
+        let error = (current - target).abs();
+        if error < best.1 {
+            best = (size, error);
+        }
+    }
+
+    best.0
+}
+
+/// Draw a line segment between two pixels using Bresenham's algorithm
+///
+/// Pixels outside the image are skipped.
+///
+/// # Arguments
+///
+/// * `image` - The image to draw into
+/// * `from` - Start pixel `(x, y)`
+/// * `to` - End pixel `(x, y)`
+/// * `color` - Color of the line
+pub(crate) fn draw_line(image: &mut RgbImage, from: (i64, i64), to: (i64, i64), color: Rgb<u8>) {
+    let width = image.width() as i64;
+    let height = image.height() as i64;
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let sx = if x < to.0 { 1 } else { -1 };
+    let sy = if y < to.1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    loop {
+        if (0..width).contains(&x) && (0..height).contains(&y) {
+            image.put_pixel(x as u32, y as u32, color);
+        }
+        if (x, y) == to {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// Render an image using an Iterated Function System
+///
+/// The image is drawn by the renderer selected by `config.render_mode` and
+/// blurred by `config.post_blur`, as in [`render_from_config`].
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image
+///
+/// # Panics
+///
+/// Panics if [`try_render`] returns an error
+pub fn render<R: Rng + Clone>(rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    try_render(rng, ifs, config).expect("Failed to render the fractal")
+}
+
+/// Render an image using an Iterated Function System, reporting failures
+///
+/// The image is drawn by the renderer selected by `config.render_mode` and
+/// blurred by `config.post_blur`, as in [`render_from_config`].
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing the RGB image, a ConfigError if a rendering setting
+/// of the configuration is invalid, an IfsError if the IFS is malformed, or
+/// a DegenerateError if no point was left to plot or the orbit diverged so
+/// the points cannot be normalized
+pub fn try_render<R: Rng + Clone>(
+    rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Result<RgbImage> {
+    config.validate_rendering()?;
+    check_ifs(ifs)?;
+
+    let Rendered {
+        mut image, bounds, ..
+    } = render_mode_with_bounds(rng, ifs, config);
+    let (x_min, x_max, y_min, y_max) = bounds;
+    if x_min > x_max || y_min > y_max {
+        return Err(Error::DegenerateError(
+            "Attractor has no points to plot".to_string(),
+        ));
+    }
+    if !(x_max - x_min).is_finite() || !(y_max - y_min).is_finite() {
+        return Err(Error::DegenerateError(
+            "Attractor has infinite extent; the orbit diverged".to_string(),
+        ));
+    }
+    blur(&mut image, config.post_blur);
+
+    Ok(image)
+}
+
+/// Check that an IFS is usable for rendering
+///
+/// Construction guarantees matching, non-empty transforms and weights, but
+/// not finite coefficients, which would send the orbit to NaN.
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+///
+/// # Returns
+///
+/// A Result containing () if the IFS is well-formed, or an IfsError
+fn check_ifs(ifs: &SigmaFactorIFS) -> Result<()> {
+    let finite = ifs
+        .transforms()
+        .iter()
+        .all(|t| t.w.iter().chain(t.b.iter()).all(|v| v.is_finite()));
+    if !finite {
+        return Err(Error::IfsError(
+            "IFS has non-finite coefficients".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Render a fractal onto an existing image
+///
+/// The fractal is rendered as by [`try_render`] and its top-left corner placed
+/// at `origin` in `base`. Pixels equal to `config.background` are skipped so
+/// the existing content shows through, and every other pixel overwrites the
+/// pixel below it; there is no alpha blending. Parts falling outside `base`
+/// are clipped.
+///
+/// # Arguments
+///
+/// * `base` - The image to draw onto
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+/// * `origin` - Position `(x, y)` of the fractal in `base`
+///
+/// # Returns
+///
+/// A Result containing () if successful, or the Error of [`try_render`], in
+/// which case `base` is left untouched
+pub fn render_overlay<R: Rng + Clone>(
+    base: &mut RgbImage,
+    rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+    origin: (u32, u32),
+) -> Result<()> {
+    let fractal = try_render(rng, ifs, config)?;
+    let background = Rgb(config.background);
+
+    for (x, y, &pixel) in fractal.enumerate_pixels() {
+        if pixel == background {
+            continue;
+        }
+        let (bx, by) = (origin.0 as u64 + x as u64, origin.1 as u64 + y as u64);
+        if bx < base.width() as u64 && by < base.height() as u64 {
+            base.put_pixel(bx as u32, by as u32, pixel);
+        }
+    }
+
+    Ok(())
+}
+
+/// An image rendered by one of the render modes
+pub(crate) struct Rendered {
+    /// The rendered image
+    pub image: RgbImage,
+
+    /// Bounding box `(x_min, x_max, y_min, y_max)` mapped to the canvas
+    pub bounds: (f64, f64, f64, f64),
+
+    /// The random Julia color the image was drawn in, or `None` if its
+    /// colors do not come from a single random draw
+    pub color: Option<Rgb<u8>>,
+
+    /// Whether any point landed on the canvas, whatever color it was drawn in
+    pub plotted: bool,
+}
+
+/// Check whether any point falls in a pixel of the canvas
+///
+/// A point falls in the pixel of its truncated coordinates, as when hits
+/// are counted for the density.
+///
+/// # Arguments
+///
+/// * `xs` - X coordinates in pixels
+/// * `ys` - Y coordinates in pixels
+/// * `width` - Width of the canvas
+/// * `height` - Height of the canvas
+///
+/// # Returns
+///
+/// True if at least one point is on the canvas
+pub(crate) fn any_on_canvas(xs: &[f64], ys: &[f64], width: usize, height: usize) -> bool {
+    xs.iter()
+        .zip(ys.iter())
+        .any(|(x, y)| (x.trunc() as usize) < width && (y.trunc() as usize) < height)
+}
+
+/// Render an image and report the raw bounding box of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+fn render_with_bounds<R: Rng + Clone>(
+    mut rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Rendered {
+    let height = config.height;
+    let width = config.width;
+
+    // Generate points
+    let points = prepare_points(&mut rng, ifs, config);
+
+    // Create image
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, Rgb(config.background));
+
+    // Draw points
+    let point_size = match config.target_coverage {
+        Some(target) => auto_point_size(&points.xs, &points.ys, width, height, target),
+        None => config.point_size,
+    };
+    let color = random_julia_color(&mut rng);
+    let n = points.xs.len().saturating_sub(1).max(1) as f64;
+    let (cx, cy) = match config.color_scheme {
+        ColorScheme::AngularHue => attractor_stats(&points.xs, &points.ys).centroid,
+        _ => (0.0, 0.0),
+    };
+    let orientation_colors: Vec<Rgb<u8>> = ifs
+        .transforms()
+        .iter()
+        .map(|t| {
+            if t.determinant().signum() < 0.0 {
+                JULIA_PURPLE
+            } else {
+                JULIA_GREEN
+            }
+        })
+        .collect();
+    let color_of = |i: usize| match config.color_scheme {
+        ColorScheme::Single | ColorScheme::WeightedTransform => color,
+        ColorScheme::Orientation => orientation_colors[points.labels[i]],
+        ColorScheme::Temporal => {
+            // Fade towards the next Julia color over the orbit
+            let index = JULIA_COLORS.iter().position(|&c| c == color).unwrap_or(0);
+            let end = JULIA_COLORS[(index + 1) % JULIA_COLORS.len()];
+            lerp_color(color, end, i as f64 / n)
+        }
+        ColorScheme::AngularHue => {
+            let angle = (points.ys[i] - cy).atan2(points.xs[i] - cx);
+            hsv_to_rgb(angle.to_degrees(), 1.0, 1.0)
+        }
+    };
+
+    // Later points overwrite earlier ones, so the draw order decides which
+    // color shows in pixels visited more than once
+    let mut order: Vec<usize> = (0..points.xs.len())
+        .step_by(config.plot_stride.max(1))
+        .collect();
+    if config.shuffle_draw {
+        order.shuffle(&mut aux_rng(config.seed, SHUFFLE_STREAM));
+    }
+    if config.color_scheme == ColorScheme::WeightedTransform {
+        blend_transform_hits(
+            &mut image,
+            &points,
+            &order,
+            ifs.transforms().len(),
+            color,
+            config,
+        );
+    } else if config.supersample > 1 {
+        plot_supersampled(
+            &mut image,
+            &points,
+            &order,
+            color_of,
+            point_size,
+            config.supersample,
+        );
+    } else {
+        for i in order {
+            plot_point(
+                &mut image,
+                points.xs[i],
+                points.ys[i],
+                color_of(i),
+                point_size,
+            );
+        }
+    }
+
+    apply_orientation(&mut image, config);
+
+    let color = match config.color_scheme {
+        ColorScheme::Orientation | ColorScheme::AngularHue => None,
+        _ => Some(color),
+    };
+    Rendered {
+        image,
+        bounds: points.bounds,
+        color,
+        plotted: any_on_canvas(&points.xs, &points.ys, width, height),
+    }
+}
+
+/// Number of fractional bits of the fixed-point coordinates of supersampled plotting
+///
+/// Pixel coordinates are scaled by 2^16, a resolution of 1/65536 pixel,
+/// which is finer than any supported supersampling factor and leaves
+/// ample headroom in an `i64` for canvases of any practical size.
+pub const FIXED_POINT_BITS: u32 = 16;
+
+/// Convert a pixel coordinate to fixed point
+///
+/// # Arguments
+///
+/// * `x` - The coordinate in pixels
+///
+/// # Returns
+///
+/// The coordinate times 2^[`FIXED_POINT_BITS`], rounded down, or `None` if
+/// it is not finite or too far off the canvas to be plotted
+fn to_fixed(x: f64) -> Option<i64> {
+    // Keeps the product with the supersampling factor well inside i64
+    const LIMIT: f64 = (1u64 << 40) as f64;
+
+    if x.is_finite() && x.abs() < LIMIT {
+        Some((x * (1u64 << FIXED_POINT_BITS) as f64).floor() as i64)
+    } else {
+        None
+    }
+}
+
+/// Plot points on a supersampled grid and average it down to the image
+///
+/// Every coordinate is converted to fixed point once, and the subpixel it
+/// falls in is found with integer arithmetic only, so the result does not
+/// depend on how a platform rounds intermediate floating-point values. A
+/// point of size `radius` fills a square of `radius * factor` subpixels
+/// around it, later points overwriting earlier ones. Each pixel is then the
+/// rounded average of its `factor * factor` subpixels, unvisited subpixels
+/// counting as the background already in the image.
+///
+/// # Arguments
+///
+/// * `image` - The image to draw into, filled with the background
+/// * `points` - The prepared points
+/// * `order` - Indices of the points to plot
+/// * `color_of` - Color of the point at an index
+/// * `radius` - Half-width of the plotted squares in pixels
+/// * `factor` - Number of subpixels per pixel along each axis
+fn plot_supersampled<F: Fn(usize) -> Rgb<u8>>(
+    image: &mut RgbImage,
+    points: &PreparedPoints,
+    order: &[usize],
+    color_of: F,
+    radius: u32,
+    factor: usize,
+) {
+    let s = factor as i64;
+    let sub_width = image.width() as i64 * s;
+    let sub_height = image.height() as i64 * s;
+    let r = radius as i64 * s;
+
+    let mut grid: Vec<Option<Rgb<u8>>> = vec![None; (sub_width * sub_height) as usize];
+    for &i in order {
+        let (Some(fx), Some(fy)) = (to_fixed(points.xs[i]), to_fixed(points.ys[i])) else {
+            continue;
+        };
+        let cx = (fx * s) >> FIXED_POINT_BITS;
+        let cy = (fy * s) >> FIXED_POINT_BITS;
+        let color = color_of(i);
+        for sy in (cy - r).max(0)..=(cy + r).min(sub_height - 1) {
+            for sx in (cx - r).max(0)..=(cx + r).min(sub_width - 1) {
+                grid[(sy * sub_width + sx) as usize] = Some(color);
+            }
+        }
+    }
+
+    let n = (s * s) as u32;
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let mut sum = [0u32; 3];
+        for sy in y as i64 * s..(y as i64 + 1) * s {
+            for sx in x as i64 * s..(x as i64 + 1) * s {
+                let color = grid[(sy * sub_width + sx) as usize].unwrap_or(*pixel);
+                for c in 0..3 {
+                    sum[c] += color[c] as u32;
+                }
+            }
+        }
+        *pixel = Rgb(sum.map(|v| ((v + n / 2) / n) as u8));
+    }
+}
+
+/// Color every pixel by the hit-weighted average of its transformations' colors
+///
+/// Transformation `k` gets the `k`-th Julia color after `first`. The total
+/// hit count of a pixel is tone-mapped to the weight of the blended color
+/// against the background.
+///
+/// # Arguments
+///
+/// * `image` - The image to draw into
+/// * `points` - The prepared points
+/// * `order` - Indices of the points to plot
+/// * `n_transforms` - Number of transformations of the IFS
+/// * `first` - Color of the first transformation
+/// * `config` - Configuration for rendering
+fn blend_transform_hits(
+    image: &mut RgbImage,
+    points: &PreparedPoints,
+    order: &[usize],
+    n_transforms: usize,
+    first: Rgb<u8>,
+    config: &Config,
+) {
+    let width = image.width() as usize;
+    let height = image.height() as usize;
+
+    // Count the hits of every transformation in every pixel
+    let mut hits = vec![0u32; width * height * n_transforms];
+    for &i in order {
+        let x = points.xs[i].trunc() as usize;
+        let y = points.ys[i].trunc() as usize;
+
+        // Check bounds
+        if x < width && y < height {
+            hits[(y * width + x) * n_transforms + points.labels[i]] += 1;
+        }
+    }
+    let totals: Vec<u32> = hits.chunks(n_transforms).map(|h| h.iter().sum()).collect();
+    let intensities = tone_map(&totals, config.tone_map);
+
+    let start = JULIA_COLORS.iter().position(|&c| c == first).unwrap_or(0);
+    let colors: Vec<Rgb<u8>> = (0..n_transforms)
+        .map(|k| JULIA_COLORS[(start + k) % JULIA_COLORS.len()])
+        .collect();
+    for (index, pixel_hits) in hits.chunks(n_transforms).enumerate() {
+        let total = totals[index];
+        if total == 0 {
+            continue;
+        }
+
+        // Average the colors weighted by the hit counts
+        let mut mix = [0.0; 3];
+        for (&count, color) in pixel_hits.iter().zip(colors.iter()) {
+            for c in 0..3 {
+                mix[c] += count as f64 * color[c] as f64;
+            }
+        }
+        let mix = Rgb(mix.map(|v| (v / total as f64).round() as u8));
+
+        let pixel = lerp_color(Rgb(config.background), mix, intensities[index] as f64);
+        image.put_pixel((index % width) as u32, (index / width) as u32, pixel);
+    }
+}
+
+/// Render an image by connecting consecutive orbit points with lines
+///
+/// The chaos game jumps between sub-attractors at every step, so the trails
+/// crisscross the whole attractor rather than tracing its outline.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_trails<R: Rng + Clone>(rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    render_trails_with_bounds(rng, ifs, config).image
+}
+
+/// Render an image of orbit trails and report the raw bounding box of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+fn render_trails_with_bounds<R: Rng + Clone>(
+    mut rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Rendered {
+    let height = config.height;
+    let width = config.width;
+
+    // Generate points
+    let points = prepare_points(&mut rng, ifs, config);
+
+    // Create image
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, Rgb(config.background));
+
+    // Draw segments between consecutive points
+    let color = random_julia_color(&mut rng);
+    let pixels: Vec<(i64, i64)> = points
+        .xs
+        .iter()
+        .zip(points.ys.iter())
+        .map(|(x, y)| (x.trunc() as i64, y.trunc() as i64))
+        .collect();
+    for segment in pixels.windows(2) {
+        draw_line(&mut image, segment[0], segment[1], color);
+    }
+
+    apply_orientation(&mut image, config);
+
+    Rendered {
+        image,
+        bounds: points.bounds,
+        color: Some(color),
+        plotted: any_on_canvas(&points.xs, &points.ys, width, height),
+    }
+}
+
+/// Render glowing halos colored by the distance to the attractor
+///
+/// The binary rendering is reduced to its [`distance_field`], and every
+/// pixel within `config.halo_radius` of a plotted point takes the color of
+/// [`julia_gradient`] at its relative distance, fading linearly into the
+/// background at the halo radius. Plotted points themselves are red, the
+/// start of the gradient.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_distance<R: Rng + Clone>(rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    render_distance_with_bounds(rng, ifs, config).image
+}
+
+/// Render distance halos and report the raw bounding box of the orbit
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+fn render_distance_with_bounds<R: Rng + Clone>(
+    rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Rendered {
+    let background = Rgb(config.background);
+    let Rendered {
+        image: binary,
+        bounds,
+        plotted,
+        ..
+    } = render_with_bounds(rng, ifs, config);
+    let distances = distance_field(&binary, background);
+
+    let width = binary.width();
+    let image = ImageBuffer::from_fn(width, binary.height(), |x, y| {
+        let t = distances[(y * width + x) as usize] as f64 / config.halo_radius;
+        if t < 1.0 {
+            lerp_color(julia_gradient(t), background, t)
+        } else {
+            background
+        }
+    });
+
+    // The halos use the gradient, not the color of the binary rendering
+    Rendered {
+        image,
+        bounds,
+        color: None,
+        plotted,
+    }
+}
+
+/// Render the first level of an IFS as a wireframe
+///
+/// This is the raster counterpart of
+/// [`skeleton_to_svg`](crate::skeleton_to_svg): every transformation maps
+/// the unit square to a parallelogram, drawn as an outline in its own Julia
+/// color, and the parallelograms are normalized together to the canvas.
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_skeleton(ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    render_skeleton_with_bounds(ifs, config).image
+}
+
+/// Render the wireframe of an IFS and report the bounding box of its parallelograms
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+fn render_skeleton_with_bounds(ifs: &SigmaFactorIFS, config: &Config) -> Rendered {
+    let height = config.height;
+    let width = config.width;
+
+    // Map the corners of the unit square through every transformation
+    let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    let (mut xs, mut ys): (Vec<f64>, Vec<f64>) = ifs
+        .transforms()
+        .iter()
+        .flat_map(|t| {
+            corners.iter().map(move |&(x, y)| {
+                let p = t.apply(&Vector2f::new(x, y));
+                (p.x, p.y)
+            })
+        })
+        .unzip();
+    let bounds = point_bounds(&xs, &ys);
+    normalize_points_to_bounds(&mut xs, &mut ys, bounds, height, width);
+
+    // Draw the outline of every parallelogram
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, Rgb(config.background));
+    for (k, (pxs, pys)) in xs.chunks(4).zip(ys.chunks(4)).enumerate() {
+        let color = JULIA_COLORS[k % JULIA_COLORS.len()];
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            draw_line(
+                &mut image,
+                (pxs[i].trunc() as i64, pys[i].trunc() as i64),
+                (pxs[j].trunc() as i64, pys[j].trunc() as i64),
+                color,
+            );
+        }
+    }
+
+    apply_orientation(&mut image, config);
+
+    Rendered {
+        image,
+        bounds,
+        color: None,
+        plotted: any_on_canvas(&xs, &ys, width, height),
+    }
+}
+
+/// Brightness added to a pixel by a single fully opaque hit in [`render_glow`]
+const GLOW_GAIN: f32 = 0.25;
+
+/// Render an image by additively blending semi-transparent points
+///
+/// Every transformation is assigned its own Julia color. Each plotted point
+/// adds its transformation's color, scaled by the color's alpha, into a
+/// floating-point buffer, so dense regions saturate toward white while
+/// sparse regions stay dim and keep their hue. Channels are clamped at 255
+/// when converting back to 8 bits.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_glow<R: Rng + Clone>(mut rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbImage {
+    let height = config.height;
+    let width = config.width;
+
+    // Generate points
+    let points = prepare_points(&mut rng, ifs, config);
+
+    // Assign a color to every transformation
+    let colors: Vec<_> = (0..ifs.transforms().len())
+        .map(|_| random_julia_color_alpha(&mut rng))
+        .collect();
+
+    // Accumulate additively on top of the background
+    let mut buffer: Vec<f32> = config
+        .background
+        .iter()
+        .cycle()
+        .take(width * height * 3)
+        .map(|&c| c as f32)
+        .collect();
+    for ((x, y), &label) in points
+        .xs
+        .iter()
+        .zip(points.ys.iter())
+        .zip(points.labels.iter())
+    {
+        let x = x.trunc() as usize;
+        let y = y.trunc() as usize;
+
+        // Check bounds
+        if x < width && y < height {
+            let color = colors[label];
+            let alpha = color[3] as f32 / 255.0;
+            let offset = 3 * (y * width + x);
+            for c in 0..3 {
+                buffer[offset + c] += GLOW_GAIN * alpha * color[c] as f32;
+            }
+        }
+    }
+
+    // Clamp back to 8 bits
+    let data = buffer.iter().map(|&v| v.min(255.0) as u8).collect();
+    let mut image = ImageBuffer::from_raw(width as u32, height as u32, data).unwrap();
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Render an image with a transparent background and translucent points
+///
+/// Every point is composited source-over onto the canvas with opacity
+/// `config.point_alpha`, clamped to [0, 1], so a pixel covered by `k`
+/// points reaches an opacity of `1 - (1 - alpha)^k`. Colors are accumulated
+/// in floating point, so even very low opacities build up without rounding
+/// loss. Composite the result with
+/// [`composite_over_gradient`](crate::composite_over_gradient) or any other
+/// background.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// An RGBA image with straight (non-premultiplied) alpha
+pub fn render_rgba<R: Rng + Clone>(mut rng: R, ifs: &SigmaFactorIFS, config: &Config) -> RgbaImage {
+    let height = config.height;
+    let width = config.width;
+
+    // Generate points
+    let points = prepare_points(&mut rng, ifs, config);
+    let color = random_julia_color(&mut rng);
+    let alpha = config.point_alpha.clamp(0.0, 1.0) as f32;
+
+    // Accumulate premultiplied colors and coverage
+    let mut buffer = vec![[0.0f32; 4]; width * height];
+    let r = config.point_size as i64;
+    for (&x, &y) in points.xs.iter().zip(points.ys.iter()) {
+        let cx = x.trunc() as i64;
+        let cy = y.trunc() as i64;
+        for py in (cy - r).max(0)..=(cy + r).min(height as i64 - 1) {
+            for px in (cx - r).max(0)..=(cx + r).min(width as i64 - 1) {
+                let dst = &mut buffer[py as usize * width + px as usize];
+                for c in 0..3 {
+                    dst[c] = color[c] as f32 * alpha + dst[c] * (1.0 - alpha);
+                }
+                dst[3] = alpha + dst[3] * (1.0 - alpha);
+            }
+        }
+    }
+
+    // Convert back to straight alpha
+    let data = buffer
+        .iter()
+        .flat_map(|&[r, g, b, a]| {
+            let unpremultiply = |v: f32| if a > 0.0 { (v / a).round() as u8 } else { 0 };
+            [
+                unpremultiply(r),
+                unpremultiply(g),
+                unpremultiply(b),
+                (a * 255.0).round() as u8,
+            ]
+        })
+        .collect();
+    let mut image = ImageBuffer::from_raw(width as u32, height as u32, data).unwrap();
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Largest number of points plotted by [`render_deterministic`]
+pub const MAX_DETERMINISTIC_POINTS: usize = 1 << 22;
+
+/// Render an image with the deterministic algorithm
+///
+/// Instead of following a random orbit, every composition of `depth`
+/// transformations is applied to the origin, giving `n^depth` points for an
+/// IFS of `n` transformations. Each point is colored by the outermost
+/// transformation, that is the sub-attractor it lies in, cycling through
+/// the Julia colors. For small IFSs this gives crisp, noise-free images.
+///
+/// # Arguments
+///
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering
+/// * `depth` - Number of transformations composed for every point; reduced
+///   to the largest depth producing at most [`MAX_DETERMINISTIC_POINTS`]
+///   points
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_deterministic(ifs: &SigmaFactorIFS, config: &Config, depth: usize) -> RgbImage {
+    let height = config.height;
+    let width = config.width;
+    let n = ifs.transforms().len();
+
+    // Apply every transformation to every point of the previous level
+    let mut points = vec![Vector2f::zeros()];
+    let mut labels = vec![0];
+    for _ in 0..depth {
+        if points.len() * n > MAX_DETERMINISTIC_POINTS {
+            break;
+        }
+        let mut next = Vec::with_capacity(points.len() * n);
+        labels.clear();
+        for (label, transform) in ifs.transforms().iter().enumerate() {
+            next.extend(points.iter().map(|p| transform.apply(p)));
+            labels.extend(std::iter::repeat_n(label, points.len()));
+        }
+        points = next;
+    }
+
+    // Normalize points to fit within the output space
+    let mut xs: Vec<f64> = points.iter().map(|p| p.x).collect();
+    let mut ys: Vec<f64> = points.iter().map(|p| p.y).collect();
+    normalize_points(&mut xs, &mut ys, height, width);
+
+    // Draw points
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, Rgb(config.background));
+    for ((&x, &y), &label) in xs.iter().zip(ys.iter()).zip(labels.iter()) {
+        let color = JULIA_COLORS[label % JULIA_COLORS.len()];
+        plot_point(&mut image, x, y, color, config.point_size);
+    }
+
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Render several IFS layers into a single image
+///
+/// All layers are normalized against a shared bounding box so that they
+/// register correctly on the canvas, and each layer is plotted in its own
+/// color, in order, so later layers are drawn on top of earlier ones. The
+/// shared canvas is zoomed into `config.roi`, if any.
+///
+/// # Arguments
+///
+/// * `layers` - The IFS and color of each layer
+/// * `config` - Configuration for rendering
+/// * `seed` - Seed for the random number generator driving all layers
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_layers(layers: &[(SigmaFactorIFS, Rgb<u8>)], config: &Config, seed: u64) -> RgbImage {
+    let height = config.height;
+    let width = config.width;
+    let mut rng = seeded_rng(seed);
+
+    // Generate every layer before normalizing any of them
+    let mut clouds: Vec<(Vec<f64>, Vec<f64>)> = layers
+        .iter()
+        .map(|(ifs, _)| {
+            let (mut xs, mut ys) = generate_raw_points(&mut rng, ifs, config.npoints);
+            project_points(&mut xs, &mut ys, config.projection);
+            (xs, ys)
+        })
+        .collect();
+
+    let bounds = clouds.iter().fold(EMPTY_BOUNDS, |acc, (xs, ys)| {
+        union_bounds(acc, point_bounds(xs, ys))
+    });
+
+    // Draw the layers in order
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, Rgb(config.background));
+    for ((xs, ys), (_, color)) in clouds.iter_mut().zip(layers.iter()) {
+        normalize_points_to_bounds(xs, ys, bounds, height, width);
+        if let Some(roi) = config.roi {
+            apply_roi(xs, ys, &mut vec![0; xs.len()], roi, height, width);
+        }
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            plot_point(&mut image, *x, *y, *color, config.point_size);
+        }
+    }
+
+    apply_orientation(&mut image, config);
+
+    image
+}
+
+/// Render the attractors of several seeds into a single image
+///
+/// Every seed gets its own IFS and orbit, generated exactly as
+/// `render_from_config` would for that seed, and placed on the canvas the
+/// same way, except that all orbits are normalized against a shared
+/// bounding box: the fixed bounds if set, otherwise the union of the
+/// bounding boxes of the orbits. Each seed is drawn in a distinct
+/// Julia color, cycling through the palette. Colors are added where
+/// attractors overlap, saturating at 255.
+///
+/// # Arguments
+///
+/// * `base_config` - Configuration shared by all seeds; its seed is ignored
+/// * `seeds` - The seeds to superimpose
+///
+/// # Returns
+///
+/// An RGB image
+pub fn render_multiseed(base_config: &Config, seeds: &[u64]) -> RgbImage {
+    let height = base_config.height;
+    let width = base_config.width;
+
+    // Generate every orbit before normalizing any of them
+    let mut clouds: Vec<(Vec<f64>, Vec<f64>, Vec<usize>)> = seeds
+        .iter()
+        .map(|&seed| {
+            let mut rng = seeded_rng(seed);
+            let ifs = ifs_from_config(&mut rng, base_config);
+            orbit_points(&mut rng, &ifs, base_config)
+        })
+        .collect();
+
+    let bounds = clouds
+        .iter_mut()
+        .fold(EMPTY_BOUNDS, |acc, (xs, ys, labels)| {
+            union_bounds(acc, frame_points(xs, ys, labels, base_config))
+        });
+
+    // Add the color of every seed to the pixels it visits
+    let background = Rgb(base_config.background);
+    let mut image = ImageBuffer::from_pixel(width as u32, height as u32, background);
+    for (i, ((xs, ys, labels), &seed)) in clouds.iter_mut().zip(seeds).enumerate() {
+        place_points(xs, ys, labels, bounds, base_config, seed);
+
+        let mut mask = RgbImage::new(width as u32, height as u32);
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            plot_point(
+                &mut mask,
+                *x,
+                *y,
+                Rgb([255, 255, 255]),
+                base_config.point_size,
+            );
+        }
+
+        let color = JULIA_COLORS[i % JULIA_COLORS.len()];
+        for (pixel, visited) in image.pixels_mut().zip(mask.pixels()) {
+            if visited.0[0] > 0 {
+                if *pixel == background {
+                    *pixel = color;
+                } else {
+                    for c in 0..3 {
+                        pixel[c] = pixel[c].saturating_add(color[c]);
+                    }
+                }
+            }
+        }
+    }
+
+    apply_orientation(&mut image, base_config);
+
+    image
+}
+
+/// Create the IFS described by a configuration
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The SigmaFactorIFS given by `config.transforms` if set, without using
+/// `rng`; otherwise a random one with `config.num_transforms`
+/// transformations, or a random count of 2 to 4 when it is not set
+///
+/// # Panics
+///
+/// Panics if the explicit transforms are invalid, which `Config::validate`
+/// reports as an error
+pub(crate) fn ifs_from_config<R: Rng>(rng: &mut R, config: &Config) -> SigmaFactorIFS {
+    if let Some(transforms) = &config.transforms {
+        return ifs_from_coefficients(transforms, config.weights.as_deref())
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    match config.num_transforms {
+        Some(n) => rand_sigma_factor_ifs_with_count(rng, n),
+        None => rand_sigma_factor_ifs(rng),
+    }
+}
+
+/// Render a tiny image with the default configuration, as a quick health check
+///
+/// # Returns
+///
+/// A Result containing a 32x32 RGB image of 500 points, or the Error of
+/// [`try_render`]
+pub fn render_smoke() -> Result<RgbImage> {
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 500,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = ifs_from_config(&mut rng, &config);
+    try_render(rng, &ifs, &config)
+}
+
+/// Render an image using a configuration file
+///
+/// An attractor that cannot be normalized or leaves the canvas blank is
+/// regenerated with the seeds `seed + 1`, `seed + 2`, ... up to
+/// `config.max_retries` times, so the output stays deterministic.
+///
+/// With `config.best_of` set to `k`, `k` candidates are rendered from the
+/// seeds `seed + i * (max_retries + 1)` and the one with the highest
+/// coverage times similarity dimension is returned, the first one winning
+/// ties. The same seed and `k` always select the same image.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing an RGB image if successful, or an Error if not
+pub fn render_from_config(config: &Config) -> Result<RgbImage> {
+    render_from_config_with_ifs(config).map(|(image, _, _, _)| image)
+}
+
+/// Render an image using a configuration file and encode it as PNG in memory
+///
+/// Nothing is written to the filesystem, which suits HTTP handlers and
+/// WebAssembly.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing the PNG bytes, the Error of [`render_from_config`],
+/// or a RenderError if encoding fails
+pub fn render_to_png_bytes(config: &Config) -> Result<Vec<u8>> {
+    let image = render_from_config(config)?;
+
+    let mut bytes = Cursor::new(Vec::new());
+    image
+        .write_to(&mut bytes, ImageFormat::Png)
+        .map_err(|e| Error::RenderError(format!("Failed to encode PNG: {}", e)))?;
+
+    Ok(bytes.into_inner())
+}
+
+/// An image rendered from a configuration: the image, the IFS that produced
+/// it, the seed it was generated with and the random color it was drawn in
+pub(crate) type RenderedFromConfig = (RgbImage, SigmaFactorIFS, u64, Option<Rgb<u8>>);
+
+/// Render an image using a configuration file and report how it was generated
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing the RGB image, the IFS that produced it, the seed it
+/// was generated with (which differs from `config.seed` after retries) and
+/// the random color it was drawn in, if any, or an Error if not
+pub(crate) fn render_from_config_with_ifs(config: &Config) -> Result<RenderedFromConfig> {
+    // Validate configuration
+    config.validate()?;
+
+    // Candidates are spaced so that their retries use distinct seeds
+    let stride = config.max_retries as u64 + 1;
+    let mut best: Option<(f64, RenderedFromConfig)> = None;
+    let mut error = None;
+    for k in 0..config.best_of.unwrap_or(1) {
+        let seed = config.seed.wrapping_add(k as u64 * stride);
+        match render_candidate(config, seed) {
+            Ok(candidate) => {
+                let score = candidate_score(&candidate.0, &candidate.1, config);
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_score, _)| score > *best_score)
+                {
+                    best = Some((score, candidate));
+                }
+            }
+            Err(err) => error = Some(err),
+        }
+    }
+
+    match (best, error) {
+        (Some((_, (mut image, ifs, seed, color))), _) => {
+            blur(&mut image, config.post_blur);
+            Ok((image, ifs, seed, color))
+        }
+        (None, Some(err)) => Err(err),
+        (None, None) => unreachable!("best_of is validated to be positive"),
+    }
+}
+
+/// Score a rendered candidate of `best_of` selection
+///
+/// The score is the fraction of the canvas covered times the similarity
+/// dimension of the IFS, capped at 2, the dimension of the plane, so that
+/// large and intricate attractors win over small or thin ones.
+///
+/// # Arguments
+///
+/// * `image` - The rendered image
+/// * `ifs` - The IFS that produced it
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// The score, higher being better
+fn candidate_score(image: &RgbImage, ifs: &SigmaFactorIFS, config: &Config) -> f64 {
+    coverage(image, config.background) * similarity_dimension(ifs).min(2.0)
+}
+
+/// Render the image of a seed, retrying with the following seeds on failure
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+/// * `first_seed` - The seed of the first attempt
+///
+/// # Returns
+///
+/// A Result containing the RGB image, the IFS that produced it, the seed it
+/// was generated with and its random color, or the Error of the last attempt
+fn render_candidate(config: &Config, first_seed: u64) -> Result<RenderedFromConfig> {
+    let mut retry = 0;
+    loop {
+        // Create RNG
+        let seed = first_seed.wrapping_add(retry as u64);
+        let mut rng = seeded_rng(seed);
+
+        // Create IFS
+        let ifs = ifs_from_config(&mut rng, config);
+
+        // Render image, rejecting attractors that cannot be normalized
+        let Rendered {
+            image,
+            bounds,
+            color,
+            plotted,
+        } = render_mode_with_bounds(rng, &ifs, config);
+        let result = check_extent(bounds)
+            .and_then(|_| check_plotted(plotted))
+            .map(|_| (image, ifs, seed, color));
+        if result.is_ok() || retry == config.max_retries {
+            return result;
+        }
+        retry += 1;
+    }
+}
+
+/// Generate the normalized point cloud rendered by [`render_from_config`]
+///
+/// The image is rendered once to resolve the seed after retries, then the
+/// orbit of that seed is regenerated, so the points are exactly those
+/// plotted in the image.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+///
+/// # Returns
+///
+/// A Result containing the x and y pixel coordinates of the points, or an
+/// Error if the image cannot be rendered
+pub fn points_from_config(config: &Config) -> Result<(Vec<f64>, Vec<f64>)> {
+    let (_, _, seed, _) = render_from_config_with_ifs(config)?;
+
+    Ok(points_from_seed(config, seed))
+}
+
+/// Generate the normalized point cloud of the IFS sampled from a seed
+///
+/// This regenerates the orbit of an image already rendered by
+/// [`render_from_config`], given the seed it was generated with (see
+/// [`RenderReport::seed`](crate::RenderReport::seed)), without rendering it
+/// again. No retries are made.
+///
+/// # Arguments
+///
+/// * `config` - Configuration for rendering
+/// * `seed` - The seed the image was generated with
+///
+/// # Returns
+///
+/// The x and y pixel coordinates of the points
+pub fn points_from_seed(config: &Config, seed: u64) -> (Vec<f64>, Vec<f64>) {
+    let mut rng = seeded_rng(seed);
+    let ifs = ifs_from_config(&mut rng, config);
+    let points = prepare_points(&mut rng, &ifs, config);
+
+    (points.xs, points.ys)
+}
+
+/// Render an image with the renderer selected by `config.render_mode`
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `ifs` - The Iterated Function System
+/// * `config` - Configuration for rendering; its render mode must be one of
+///   [`RENDER_MODES`](crate::core::config::RENDER_MODES)
+///
+/// # Returns
+///
+/// The image, the bounding box that was mapped to the canvas and the color
+/// drawn
+fn render_mode_with_bounds<R: Rng + Clone>(
+    rng: R,
+    ifs: &SigmaFactorIFS,
+    config: &Config,
+) -> Rendered {
+    match config.render_mode.as_str() {
+        "binary" => render_with_bounds(rng, ifs, config),
+        "density" => render_density_with_bounds(rng, ifs, config),
+        "trails" => render_trails_with_bounds(rng, ifs, config),
+        "skeleton" => render_skeleton_with_bounds(ifs, config),
+        "splat" => render_splat_with_bounds(rng, ifs, config),
+        "distance" => render_distance_with_bounds(rng, ifs, config),
+        mode => unreachable!("Unknown render mode {} passed validation", mode),
+    }
+}
+
+/// Check that a rendering plotted anything on the canvas
+///
+/// The points are checked rather than the image, so an attractor drawn in
+/// the background color does not count as blank.
+///
+/// # Arguments
+///
+/// * `plotted` - Whether any point landed on the canvas
+///
+/// # Returns
+///
+/// A Result containing () if any point was plotted, or an Error if not
+fn check_plotted(plotted: bool) -> Result<()> {
+    if !plotted {
+        return Err(Error::DegenerateError(
+            "Attractor covers no pixels of the canvas".to_string(),
+        ));
+    }
 
-    // Render image
-    Ok(render(rng, &ifs, config))
+    Ok(())
 }
@@ -1,6 +1,7 @@
 //! Common types used throughout the library
 
 use nalgebra::{Matrix2, Vector2};
+use serde::{Deserialize, Serialize};
 
 /// Type alias for a 2D matrix
 pub type Matrix2f = Matrix2<f64>;
@@ -8,6 +9,9 @@ pub type Matrix2f = Matrix2<f64>;
 /// Type alias for a 2D vector
 pub type Vector2f = Vector2<f64>;
 
+/// Type alias for the library's default random number generator
+pub type DefaultRng = rand_xoshiro::Xoshiro256PlusPlus;
+
 /// Trait for Iterated Function Systems
 pub trait IFS {
     /// The dimension of the IFS
@@ -18,4 +22,139 @@ pub trait IFS {
 
     /// Apply a random transformation to a point
     fn apply_random<R: rand::Rng>(&self, rng: &mut R, point: &Vector2f) -> Vector2f;
+
+    /// Get the number of transformations of the IFS
+    fn num_transforms(&self) -> usize;
+}
+
+/// Object-safe interface for Iterated Function Systems
+///
+/// [`IFS`] is generic over the random number generator and has associated
+/// items, so it cannot be used as a trait object. `DynIFS` takes the
+/// generator as `&mut dyn RngCore` instead, and is implemented for every
+/// `IFS`, so heterogeneous systems can be stored as `Vec<Box<dyn DynIFS>>`.
+pub trait DynIFS {
+    /// Apply a random transformation to a point
+    fn apply_random_dyn(&self, rng: &mut dyn rand::RngCore, point: &Vector2f) -> Vector2f;
+
+    /// Get the number of transformations of the IFS
+    fn num_transforms_dyn(&self) -> usize;
+}
+
+impl<T: IFS> DynIFS for T {
+    fn apply_random_dyn(&self, mut rng: &mut dyn rand::RngCore, point: &Vector2f) -> Vector2f {
+        self.apply_random(&mut rng, point)
+    }
+
+    fn num_transforms_dyn(&self) -> usize {
+        self.num_transforms()
+    }
+}
+
+/// Projection applied to the raw orbit before rasterization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Projection {
+    /// Plot points in the plane as they are
+    #[default]
+    Cartesian,
+
+    /// Plot points in log-polar coordinates
+    ///
+    /// A point z = (x, y) is mapped to (ln|z|, arg z), which turns rotations
+    /// and scalings about the origin into translations and reveals spiral
+    /// structure. Points exactly at the origin, where ln|z| is undefined,
+    /// are skipped.
+    LogPolar,
+}
+
+/// Symmetry imposed on the orbit before rasterization
+///
+/// The symmetric images of every point are added to the cloud, taken about
+/// the centroid of the attractor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Symmetry {
+    /// Mirror across the vertical axis, so the left and right halves match
+    MirrorX,
+
+    /// Mirror across the horizontal axis, so the top and bottom halves match
+    MirrorY,
+
+    /// Rotate by every multiple of `360 / n` degrees
+    Rotational(u32),
+}
+
+/// Tone mapping operator converting hit counts to intensities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ToneMap {
+    /// Intensity proportional to the hit count
+    Linear,
+
+    /// Intensity proportional to the logarithm of the hit count
+    #[default]
+    Log,
+
+    /// Reinhard operator `x / (1 + x)`, with `x` the hit count relative to
+    /// the mean visited count, giving a soft highlight rolloff
+    Reinhard,
+
+    /// Intensity proportional to the square root of the hit count
+    Sqrt,
+
+    /// Full intensity for every visited pixel, regardless of its count
+    Binary,
+}
+
+/// Resampling filter used when resizing rendered images
+///
+/// Fractals are made of thin, high-contrast filaments. `Nearest` keeps them
+/// sharp but aliases when downscaling, `Triangle` and `Gaussian` blur them
+/// into soft strokes, while `CatmullRom` and `Lanczos3` preserve the most
+/// detail at the cost of slight ringing around isolated points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FilterKind {
+    /// Nearest neighbor sampling
+    Nearest,
+
+    /// Linear interpolation
+    Triangle,
+
+    /// Cubic Catmull-Rom spline
+    CatmullRom,
+
+    /// Gaussian kernel
+    Gaussian,
+
+    /// Lanczos kernel with a window of 3
+    #[default]
+    Lanczos3,
+}
+
+/// How the points of a binary rendering are colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorScheme {
+    /// Every point gets the same random Julia color
+    #[default]
+    Single,
+
+    /// Points fade from a random Julia color to the next one over the
+    /// course of the orbit. Points are drawn in iteration order, so the
+    /// color of each pixel reflects its most recent visit.
+    Temporal,
+
+    /// Points are colored on a hue wheel by their angle around the
+    /// centroid of the attractor
+    AngularHue,
+
+    /// Every transformation gets its own Julia color, and each pixel shows
+    /// the average color of the transformations that visited it, weighted
+    /// by their hit counts, so overlapping sub-attractors mix. The total
+    /// count sets the brightness through the configured tone mapping.
+    /// Points are plotted as single pixels regardless of the point size.
+    WeightedTransform,
+
+    /// Points are colored by the orientation of the transformation that
+    /// produced them: green for orientation-preserving transformations,
+    /// whose determinant is positive, and purple for reflections, whose
+    /// determinant is negative
+    Orientation,
 }
@@ -1,7 +1,105 @@
 //! Utility functions for the rust-random-logo library
 
 use image::{Rgb, Rgba};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::core::types::DefaultRng;
+
+/// Create the library's default random number generator from a seed
+///
+/// This returns the same generator `render_from_config` uses for
+/// `rng_name = "Xoshiro256PlusPlus"`, so rendering with it reproduces the
+/// output of the configuration path.
+///
+/// # Arguments
+///
+/// * `seed` - Seed for the random number generator
+///
+/// # Returns
+///
+/// A seeded random number generator
+pub fn seeded_rng(seed: u64) -> DefaultRng {
+    DefaultRng::seed_from_u64(seed)
+}
+
+/// Create an auxiliary random stream derived from a seed
+///
+/// Stream `k` starts `k` long jumps (2^192 steps each) ahead of
+/// `seeded_rng(seed)`, so options that need their own randomness, such as
+/// jitter, never overlap with nor shift the stream that chooses the geometry.
+///
+/// # Arguments
+///
+/// * `seed` - The seed
+/// * `stream` - Index of the stream; 0 is `seeded_rng(seed)` itself
+///
+/// # Returns
+///
+/// A random number generator for the stream
+pub(crate) fn aux_rng(seed: u64, stream: u32) -> DefaultRng {
+    let mut rng = seeded_rng(seed);
+    for _ in 0..stream {
+        rng.long_jump();
+    }
+    rng
+}
+
+/// Internal state of the default random number generator
+#[derive(Deserialize)]
+struct DefaultRngState {
+    s: [u64; 4],
+}
+
+/// Export the exact state of the default random number generator
+///
+/// Restoring the bytes with [`rng_from_bytes`] yields a generator that
+/// produces exactly the same stream as `rng` from this point on, which
+/// allows checkpointing a long generation and resuming it later.
+///
+/// # Arguments
+///
+/// * `rng` - The generator to snapshot
+///
+/// # Returns
+///
+/// The 256-bit state as 32 little-endian bytes
+///
+/// # Panics
+///
+/// Panics if the serialized form of the generator no longer holds its four
+/// state words, which only a change in `rand_xoshiro` could cause; the
+/// dependency is pinned for this reason
+pub fn rng_state_to_bytes(rng: &DefaultRng) -> [u8; 32] {
+    // rand_xoshiro exposes the state only through serde
+    let value = serde_json::to_value(rng).expect("generator state is serializable");
+    let state: DefaultRngState =
+        serde_json::from_value(value).expect("generator state has four words");
+
+    let mut bytes = [0u8; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(8).zip(state.s) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    assert!(
+        rng_from_bytes(bytes) == *rng,
+        "exported state does not restore the generator"
+    );
+    bytes
+}
+
+/// Restore the default random number generator from an exported state
+///
+/// # Arguments
+///
+/// * `bytes` - The state produced by [`rng_state_to_bytes`]
+///
+/// # Returns
+///
+/// A generator continuing the stream of the snapshotted one
+pub fn rng_from_bytes(bytes: [u8; 32]) -> DefaultRng {
+    // The seed of Xoshiro256PlusPlus is its little-endian state
+    DefaultRng::from_seed(bytes)
+}
 
 /// Generate a random number in the range [a, b]
 ///
@@ -24,6 +122,9 @@ pub const JULIA_GREEN: Rgb<u8> = Rgb([56, 152, 38]);
 pub const JULIA_BLUE: Rgb<u8> = Rgb([64, 99, 216]);
 pub const JULIA_PURPLE: Rgb<u8> = Rgb([149, 88, 178]);
 
+/// The four Julia colors, in the order red, green, blue, purple
+pub const JULIA_COLORS: [Rgb<u8>; 4] = [JULIA_RED, JULIA_GREEN, JULIA_BLUE, JULIA_PURPLE];
+
 /// Julia colors with alpha channel
 pub const JULIA_RED_ALPHA: Rgba<u8> = Rgba([203, 60, 51, 255]);
 pub const JULIA_GREEN_ALPHA: Rgba<u8> = Rgba([56, 152, 38, 255]);
@@ -48,6 +149,80 @@ pub fn random_julia_color<R: Rng>(rng: &mut R) -> Rgb<u8> {
     }
 }
 
+/// Linearly interpolate between two colors
+///
+/// # Arguments
+///
+/// * `from` - Color at `t = 0`
+/// * `to` - Color at `t = 1`
+/// * `t` - Interpolation parameter, clamped to [0, 1]
+///
+/// # Returns
+///
+/// The interpolated color
+pub(crate) fn lerp_color(from: Rgb<u8>, to: Rgb<u8>, t: f64) -> Rgb<u8> {
+    let t = t.clamp(0.0, 1.0);
+    Rgb(std::array::from_fn(|c| {
+        let a = from[c] as f64;
+        let b = to[c] as f64;
+        (a + (b - a) * t).round() as u8
+    }))
+}
+
+/// Sample a continuous gradient through the four Julia colors
+///
+/// The gradient cycles red, green, blue, purple and back to red, with the
+/// stops at `t = 0`, `0.25`, `0.5` and `0.75`. Values outside [0, 1] wrap
+/// around, so the gradient can be sampled with any periodic parameter.
+///
+/// # Arguments
+///
+/// * `t` - Position along the gradient
+///
+/// # Returns
+///
+/// The interpolated color
+pub fn julia_gradient(t: f64) -> Rgb<u8> {
+    let n = JULIA_COLORS.len();
+    let pos = t.rem_euclid(1.0) * n as f64;
+    let index = (pos.floor() as usize).min(n - 1);
+    let from = JULIA_COLORS[index];
+    let to = JULIA_COLORS[(index + 1) % n];
+    lerp_color(from, to, pos - index as f64)
+}
+
+/// Convert a color from HSV to RGB
+///
+/// # Arguments
+///
+/// * `hue` - Hue in degrees; values outside [0, 360) wrap around
+/// * `saturation` - Saturation in [0, 1]
+/// * `value` - Value in [0, 1]
+///
+/// # Returns
+///
+/// The RGB color
+pub fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> Rgb<u8> {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let s = saturation.clamp(0.0, 1.0);
+    let v = value.clamp(0.0, 1.0);
+
+    let chroma = v * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = v - chroma;
+    let to_u8 = |c: f64| ((c + m) * 255.0).round() as u8;
+
+    Rgb([to_u8(r), to_u8(g), to_u8(b)])
+}
+
 /// Get a random Julia color with alpha channel
 ///
 /// # Arguments
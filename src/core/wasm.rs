@@ -0,0 +1,27 @@
+//! WebAssembly entry points for the rust-random-logo library
+//!
+//! This module provides functions that take and return plain data, so they
+//! can be wrapped with `wasm-bindgen` for use in the browser. They neither
+//! touch the filesystem nor spawn threads; build with
+//! `--no-default-features --features wasm` to also leave out rayon.
+
+use crate::core::config::Config;
+use crate::core::renderer::render_to_png_bytes;
+use crate::error::Result;
+
+/// Render an image from a JSON configuration and encode it as PNG
+///
+/// # Arguments
+///
+/// * `json` - The configuration as a JSON object with the same keys as the
+///   TOML configuration files
+///
+/// # Returns
+///
+/// A Result containing the PNG bytes, a JsonError if the configuration
+/// cannot be parsed, or the Error of [`render_to_png_bytes`]
+pub fn render_config_json(json: &str) -> Result<Vec<u8>> {
+    let config: Config = serde_json::from_str(json)?;
+
+    render_to_png_bytes(&config)
+}
@@ -22,9 +22,25 @@ pub enum Error {
     #[error("I/O error: {0}")]
     IoError(#[from] io::Error),
 
+    /// Error when serializing or parsing JSON
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// Error when encoding or decoding an image
+    #[error("Image error: {0}")]
+    ImageError(#[from] image::ImageError),
+
     /// Error when rendering an image
     #[error("Failed to render image: {0}")]
     RenderError(String),
+
+    /// Error when generating an Iterated Function System
+    #[error("Failed to generate IFS: {0}")]
+    IfsError(String),
+
+    /// Error when the generated attractor collapses or diverges
+    #[error("Degenerate attractor: {0}")]
+    DegenerateError(String),
 }
 
 /// Result type for the rust-random-logo library
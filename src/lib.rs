@@ -32,7 +32,60 @@ pub mod error;
 
 // Re-export commonly used items
 pub use core::affine::Affine;
-pub use core::config::Config;
-pub use core::ifs::{rand_sigma_factor_ifs, sample_svs, SigmaFactorIFS};
-pub use core::renderer::{generate_points, render, render_from_config};
+pub use core::analysis::{
+    attractor_stats, convex_hull, coverage, detect_symmetry, ifs_distance, is_degenerate,
+    luminance_histogram, similarity_dimension, AttractorStats, SymmetryReport, SYMMETRY_GRID_SIZE,
+    SYMMETRY_THRESHOLD,
+};
+pub use core::animation::{interpolate_ifs, render_frames};
+#[cfg(feature = "gif")]
+pub use core::animation::{render_gif, GIF_FRAME_DELAY_MS};
+pub use core::basins::render_basins;
+pub use core::batch::{
+    batch_output_path, find_seeds, render_batch_to_dir, render_grid, BatchSummary, SeedResult,
+};
+pub use core::cloud::PointCloud;
+pub use core::config::{
+    Config, DEFAULT_MAX_MEMORY_BYTES, MAX_ROTATIONAL_SYMMETRY, MAX_SUPERSAMPLE, RENDER_MODES,
+};
+#[cfg(feature = "exr")]
+pub use core::density::save_density_exr;
+pub use core::density::{
+    density_buffer, equalize, quantize, render_density, render_density_16,
+    render_density_16_from_seed, render_gray, render_splat, save_density, tone_map,
+    EQUALIZE_LEVELS,
+};
+pub use core::export::{
+    points_from_bytes, points_to_bytes, skeleton_to_svg, write_pgm, write_points_csv, write_ppm,
+};
+pub use core::ifs::{
+    ifs_from_coefficients, rand_contractive_ifs, rand_sigma_factor_ifs,
+    rand_sigma_factor_ifs_with_count, rand_sigma_factor_ifs_with_translation_range, sample_svs,
+    SigmaFactorIFS,
+};
+pub use core::manifest::{
+    generate_with_manifest, render_reporting, Manifest, RenderReport, TransformRecord,
+};
+#[cfg(feature = "parallel")]
+pub use core::parallel::render_parallel;
+pub use core::postprocess::{
+    apply_curves, blur, composite_over_gradient, distance_field, flip_horizontal, flip_vertical,
+    identity_curve, levels_curve, local_contrast, quantize_to_palette, resize, rotate_90, s_curve,
+    tile_preview,
+};
+pub use core::renderer::{
+    attractor_bounds, auto_point_size, check_extent, estimate_bounds, fold_orbit,
+    generate_clamped_points, generate_labeled_points, generate_points, generate_points_multistart,
+    normalize_points, point_bounds, points_from_config, points_from_seed, project_points, render,
+    render_deterministic, render_distance, render_from_config, render_glow, render_layers,
+    render_multiseed, render_overlay, render_rgba, render_skeleton, render_smoke,
+    render_to_png_bytes, render_trails, sample_attractor_point, try_render, FIXED_POINT_BITS,
+    MAX_AUTO_POINT_SIZE, MAX_DETERMINISTIC_POINTS, MULTISTART_WARMUP,
+};
+pub use core::types::{
+    ColorScheme, DefaultRng, DynIFS, FilterKind, Projection, Symmetry, ToneMap, IFS,
+};
+pub use core::utils::{hsv_to_rgb, julia_gradient, rng_from_bytes, rng_state_to_bytes, seeded_rng};
+#[cfg(feature = "wasm")]
+pub use core::wasm::render_config_json;
 pub use error::{Error, Result};
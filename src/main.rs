@@ -1,23 +1,82 @@
 //! Command-line interface for the rust-random-logo library
 
 use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
 use std::process;
 
-use rust_random_logo::{render_from_config, Config};
+use rust_random_logo::{
+    points_from_seed, render_density_16_from_seed, render_from_config, render_reporting,
+    render_smoke, write_points_csv, Config,
+};
 
 fn main() {
     // Parse command-line arguments
     let args: Vec<String> = env::args().collect();
+    let usage = format!(
+        "Usage: {} <config_file|-> [output_file] [--seed <u64>] [--points-csv <path>] [--report]\n       {} --smoke",
+        args[0], args[0]
+    );
 
-    if args.len() < 2 {
-        eprintln!("Usage: {} <config_file> [output_file]", args[0]);
+    let mut positional = Vec::new();
+    let mut seed_override = None;
+    let mut points_csv = None;
+    let mut report = false;
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = iter.next().and_then(|v| v.parse::<u64>().ok());
+                match value {
+                    Some(seed) => seed_override = Some(seed),
+                    None => {
+                        eprintln!("--seed expects an unsigned integer\n{}", usage);
+                        process::exit(1);
+                    }
+                }
+            }
+            "--points-csv" => match iter.next() {
+                Some(path) => points_csv = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("--points-csv expects a path\n{}", usage);
+                    process::exit(1);
+                }
+            },
+            "--report" => report = true,
+            "--smoke" => {
+                // Render a tiny image without any configuration or output file
+                let image = match render_smoke() {
+                    Ok(image) => image,
+                    Err(err) => {
+                        eprintln!("Smoke test failed: {}", err);
+                        process::exit(1);
+                    }
+                };
+                println!(
+                    "Smoke test passed ({}x{} image)",
+                    image.width(),
+                    image.height()
+                );
+                return;
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    if positional.is_empty() || positional.len() > 2 {
+        eprintln!("{}", usage);
         process::exit(1);
     }
 
-    // Load configuration
-    let config_path = &args[1];
-    let config = match Config::from_file(config_path) {
+    // Load configuration, reading from stdin when the path is "-"
+    let config_path = &positional[0];
+    let loaded = if config_path == "-" {
+        Config::from_reader(io::stdin().lock())
+    } else {
+        Config::from_file(config_path)
+    };
+    let mut config = match loaded {
         Ok(config) => config,
         Err(err) => {
             eprintln!("Error loading configuration: {}", err);
@@ -25,21 +84,43 @@ fn main() {
         }
     };
 
+    // The command-line seed takes precedence over the configuration file
+    if let Some(seed) = seed_override {
+        config.seed = seed;
+    }
+
     // Determine output path
-    let output_path = if args.len() >= 3 {
-        PathBuf::from(&args[2])
+    let output_path = if positional.len() >= 2 {
+        PathBuf::from(config.expand_template(&positional[1]))
     } else {
         let mut path = PathBuf::from("fractal.png");
-        if let Some(parent) = PathBuf::from(config_path).parent() {
-            path = parent.join(path);
+        if config_path != "-" {
+            if let Some(parent) = PathBuf::from(config_path).parent() {
+                path = parent.join(path);
+            }
         }
         path
     };
 
+    // 16-bit images are rendered again from the seed, which only the density
+    // renderer supports, and without the blur
+    let wide = config.bit_depth == 16;
+    if wide && (config.render_mode != "density" || config.post_blur > 0.0) {
+        eprintln!("bit_depth = 16 requires render_mode = \"density\" and no post_blur");
+        process::exit(1);
+    }
+
     // Render image
     println!("Rendering fractal with {} points...", config.npoints);
-    let image = match render_from_config(&config) {
-        Ok(image) => image,
+    // The report resolves the seed the point cloud and 16-bit image are
+    // regenerated from
+    let rendered = if report || points_csv.is_some() || wide {
+        render_reporting(&config).map(|(image, report)| (image, Some(report)))
+    } else {
+        render_from_config(&config).map(|image| (image, None))
+    };
+    let (image, render_report) = match rendered {
+        Ok(rendered) => rendered,
         Err(err) => {
             eprintln!("Error rendering image: {}", err);
             process::exit(1);
@@ -48,10 +129,51 @@ fn main() {
 
     // Save image
     println!("Saving image to {}...", output_path.display());
-    if let Err(err) = image.save(&output_path) {
+    let saved = match &render_report {
+        Some(render_report) if wide => {
+            render_density_16_from_seed(&config, render_report.seed).save(&output_path)
+        }
+        _ => image.save(&output_path),
+    };
+    if let Err(err) = saved {
         eprintln!("Error saving image: {}", err);
         process::exit(1);
     }
 
+    // Save the provenance of the image next to it
+    if let Some(render_report) = render_report.as_ref().filter(|_| report) {
+        let report_path = output_path.with_extension("json");
+        println!(
+            "Seed {}, color {:?}, {} transforms, coverage {:.3}",
+            render_report.seed,
+            render_report.color,
+            render_report.num_transforms,
+            render_report.coverage
+        );
+        println!("Saving report to {}...", report_path.display());
+        let written = render_report
+            .to_json()
+            .map_err(|err| err.to_string())
+            .and_then(|json| fs::write(&report_path, json).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            eprintln!("Error saving report: {}", err);
+            process::exit(1);
+        }
+    }
+
+    // Save the normalized point cloud
+    if let (Some(csv_path), Some(render_report)) = (points_csv, render_report) {
+        println!("Saving points to {}...", csv_path.display());
+        let (xs, ys) = points_from_seed(&config, render_report.seed);
+        let written = File::create(&csv_path).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            write_points_csv(&xs, &ys, &mut writer).and_then(|_| writer.flush())
+        });
+        if let Err(err) = written {
+            eprintln!("Error saving points: {}", err);
+            process::exit(1);
+        }
+    }
+
     println!("Done!");
 }
@@ -3,7 +3,29 @@
 use rand::SeedableRng;
 use rand_xoshiro::Xoshiro256PlusPlus;
 
-use rust_random_logo::{rand_sigma_factor_ifs, render, render_from_config, Config};
+use rust_random_logo::{
+    apply_curves, attractor_bounds, attractor_stats, auto_point_size, batch_output_path, blur,
+    composite_over_gradient, convex_hull, coverage, density_buffer, detect_symmetry,
+    distance_field, equalize, estimate_bounds, find_seeds, flip_horizontal, flip_vertical,
+    fold_orbit, generate_clamped_points, generate_labeled_points, generate_points,
+    generate_points_multistart, generate_with_manifest, hsv_to_rgb, identity_curve, ifs_distance,
+    ifs_from_coefficients, interpolate_ifs, is_degenerate, julia_gradient, levels_curve,
+    local_contrast, luminance_histogram, normalize_points, point_bounds, points_from_bytes,
+    points_from_config, points_from_seed, points_to_bytes, project_points, quantize,
+    quantize_to_palette, rand_contractive_ifs, rand_sigma_factor_ifs,
+    rand_sigma_factor_ifs_with_count, rand_sigma_factor_ifs_with_translation_range, render,
+    render_basins, render_batch_to_dir, render_density, render_density_16,
+    render_density_16_from_seed, render_deterministic, render_distance, render_frames,
+    render_from_config, render_glow, render_gray, render_grid, render_layers, render_multiseed,
+    render_overlay, render_reporting, render_rgba, render_skeleton, render_smoke, render_splat,
+    render_to_png_bytes, render_trails, resize, rng_from_bytes, rng_state_to_bytes, rotate_90,
+    s_curve, sample_attractor_point, save_density, seeded_rng, similarity_dimension,
+    skeleton_to_svg, tile_preview, tone_map, try_render, write_pgm, write_points_csv, write_ppm,
+    Affine, ColorScheme, Config, DynIFS, Error, FilterKind, PointCloud, Projection, SeedResult,
+    SigmaFactorIFS, Symmetry, ToneMap, DEFAULT_MAX_MEMORY_BYTES, EQUALIZE_LEVELS, IFS,
+    MAX_AUTO_POINT_SIZE, MAX_DETERMINISTIC_POINTS, MAX_ROTATIONAL_SYMMETRY, MAX_SUPERSAMPLE,
+    RENDER_MODES,
+};
 
 #[test]
 fn test_render_with_config() {
@@ -16,6 +38,7 @@ fn test_render_with_config() {
         ndims: 2,
         rng_name: "Xoshiro256PlusPlus".to_string(),
         seed: 42,
+        ..Config::default()
     };
 
     // Create a random number generator with a seed
@@ -41,10 +64,10 @@ fn test_generate_ifs() {
     let ifs = rand_sigma_factor_ifs(&mut rng);
 
     // Check that the IFS has at least one transformation
-    assert!(!ifs.transforms.is_empty());
+    assert!(!ifs.transforms().is_empty());
 
     // Check that the weights sum to approximately 1.0
-    let sum: f64 = ifs.weights.iter().sum();
+    let sum: f64 = ifs.weights().iter().sum();
     assert!((sum - 1.0).abs() < 1e-10);
 }
 
@@ -59,6 +82,7 @@ fn test_config_serialization() {
         ndims: 2,
         rng_name: "Xoshiro256PlusPlus".to_string(),
         seed: 42,
+        ..Config::default()
     };
 
     // Serialize to TOML
@@ -89,6 +113,7 @@ fn test_basic_example_and_main_program_consistency() {
         ndims: 2,
         rng_name: "Xoshiro256PlusPlus".to_string(),
         seed: 99,
+        ..Config::default()
     };
 
     // Method 1: Generate image using the basic example approach
@@ -110,3 +135,2995 @@ fn test_basic_example_and_main_program_consistency() {
         }
     }
 }
+
+#[test]
+fn test_rand_contractive_ifs() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+
+    // A contractive IFS should be found well within 100 attempts
+    let ifs = rand_contractive_ifs(&mut rng, 100).unwrap();
+    assert!(ifs.is_contractive());
+    for t in ifs.transforms() {
+        assert!(t.contraction_ratio() < 1.0);
+    }
+
+    // Zero attempts can never succeed
+    assert!(rand_contractive_ifs(&mut rng, 0).is_err());
+}
+
+#[test]
+fn test_write_ppm_and_pgm() {
+    let config = Config {
+        height: 20,
+        width: 30,
+        npoints: 500,
+        ..Config::default()
+    };
+    let image = render_from_config(&config).unwrap();
+
+    // Write the PPM and read the header back
+    let mut ppm = Vec::new();
+    write_ppm(&image, &mut ppm).unwrap();
+    let header = b"P6\n30 20\n255\n";
+    assert!(ppm.starts_with(header));
+    assert_eq!(&ppm[header.len()..], image.as_raw().as_slice());
+
+    // Write a PGM from a density buffer
+    let density: Vec<u32> = (0..6).collect();
+    let mut pgm = Vec::new();
+    write_pgm(&density, 3, 2, &mut pgm).unwrap();
+    let header = b"P5\n3 2\n255\n";
+    assert!(pgm.starts_with(header));
+    assert_eq!(&pgm[header.len()..], &[0, 51, 102, 153, 204, 255]);
+
+    // Mismatched dimensions are rejected
+    assert!(write_pgm(&density, 4, 2, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_config_from_reader() {
+    let toml = r#"
+        height = 64
+        width = 32
+        npoints = 1000
+        ifs_name = "SigmaFactorIFS"
+        ndims = 2
+        rng_name = "Xoshiro256PlusPlus"
+        seed = 7
+    "#;
+
+    let config = Config::from_reader(toml.as_bytes()).unwrap();
+    assert_eq!(config.height, 64);
+    assert_eq!(config.width, 32);
+    assert_eq!(config.seed, 7);
+
+    // Invalid TOML is reported as an error
+    assert!(Config::from_reader("height = ".as_bytes()).is_err());
+}
+
+#[test]
+fn test_interpolate_ifs() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    let a = rand_sigma_factor_ifs(&mut rng);
+    let b = loop {
+        let b = rand_sigma_factor_ifs(&mut rng);
+        if b.transforms().len() == a.transforms().len() {
+            break b;
+        }
+    };
+
+    // The endpoints reproduce the keyframes
+    let start = interpolate_ifs(&a, &b, 0.0);
+    let end = interpolate_ifs(&a, &b, 1.0);
+    for i in 0..a.transforms().len() {
+        assert!((start.transforms()[i].w - a.transforms()[i].w).norm() < 1e-12);
+        assert!((end.transforms()[i].b - b.transforms()[i].b).norm() < 1e-12);
+    }
+
+    // Interpolated weights stay normalized
+    let mid = interpolate_ifs(&a, &b, 0.5);
+    let sum: f64 = mid.weights().iter().sum();
+    assert!((sum - 1.0).abs() < 1e-10);
+
+    // One frame is rendered per step
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 500,
+        ..Config::default()
+    };
+    let frames = render_frames(rng, &a, &b, 3, &config).unwrap();
+    assert_eq!(frames.len(), 3);
+}
+
+#[test]
+fn test_attractor_bounds() {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let (x_min, x_max, y_min, y_max) = attractor_bounds(&mut rng.clone(), &ifs, 1000);
+    assert!(x_min < x_max);
+    assert!(y_min < y_max);
+
+    // The same orbit normalized to the canvas spans the full offset range
+    let (xs, ys) = generate_points(&mut rng, &ifs, 1000, 100, 100);
+    let (nx_min, nx_max, ny_min, ny_max) = point_bounds(&xs, &ys);
+    assert!((nx_min - 5.0).abs() < 1e-9 && (nx_max - 95.0).abs() < 1e-9);
+    assert!((ny_min - 5.0).abs() < 1e-9 && (ny_max - 95.0).abs() < 1e-9);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_render_parallel_is_thread_count_independent() {
+    use rust_random_logo::render_parallel;
+
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 20_000,
+        ..Config::default()
+    };
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let single = render_parallel(rng.clone(), &ifs, &config, 8, 1).unwrap();
+    for threads in [2, 4, 8] {
+        let multi = render_parallel(rng.clone(), &ifs, &config, 8, threads).unwrap();
+        assert_eq!(single.as_raw(), multi.as_raw());
+    }
+
+    // Something was actually drawn
+    assert!(single.pixels().any(|p| p.0 != [0, 0, 0]));
+
+    // The region of interest zooms into the canvas
+    let full = Config {
+        roi: Some((0.0, 1.0, 0.0, 1.0)),
+        ..config.clone()
+    };
+    assert_eq!(
+        render_parallel(rng.clone(), &ifs, &full, 8, 2).unwrap(),
+        single
+    );
+    let quarter = Config {
+        roi: Some((0.0, 0.5, 0.0, 0.5)),
+        ..config.clone()
+    };
+    assert_ne!(render_parallel(rng, &ifs, &quarter, 8, 2).unwrap(), single);
+}
+
+#[test]
+fn test_log_polar_projection() {
+    // The origin is skipped and other points map to (ln|z|, arg z)
+    let mut xs = vec![0.0, 1.0, 0.0];
+    let mut ys = vec![0.0, 0.0, std::f64::consts::E];
+    project_points(&mut xs, &mut ys, Projection::LogPolar);
+    assert_eq!(xs.len(), 2);
+    assert!((xs[0] - 0.0).abs() < 1e-12 && (ys[0] - 0.0).abs() < 1e-12);
+    assert!((xs[1] - 1.0).abs() < 1e-12);
+    assert!((ys[1] - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+    // Rendering with the projection selected from the config works
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 1000,
+        projection: Projection::LogPolar,
+        ..Config::default()
+    };
+    let image = render_from_config(&config).unwrap();
+    assert!(image.pixels().any(|p| p.0 != [0, 0, 0]));
+}
+
+#[test]
+fn test_render_layers() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 2000,
+        ..Config::default()
+    };
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+    let red = image::Rgb([255, 0, 0]);
+    let green = image::Rgb([0, 255, 0]);
+    let layers = vec![
+        (rand_sigma_factor_ifs(&mut rng), red),
+        (rand_sigma_factor_ifs(&mut rng), green),
+    ];
+
+    let image = render_layers(&layers, &config, 7);
+    assert_eq!(image.width(), 64);
+    assert!(image.pixels().any(|p| *p == green));
+
+    // Rendering is reproducible for the same seed
+    assert_eq!(image, render_layers(&layers, &config, 7));
+
+    // The region of interest zooms into the shared canvas
+    let full = Config {
+        roi: Some((0.0, 1.0, 0.0, 1.0)),
+        ..config.clone()
+    };
+    assert_eq!(render_layers(&layers, &full, 7), image);
+    let quarter = Config {
+        roi: Some((0.0, 0.5, 0.0, 0.5)),
+        ..config.clone()
+    };
+    assert_ne!(render_layers(&layers, &quarter, 7), image);
+}
+
+#[test]
+fn test_single_point_cloud() {
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 1,
+        ..Config::default()
+    };
+
+    // The configuration path reports the degenerate attractor
+    let result = render_from_config(&config);
+    assert!(matches!(result, Err(Error::DegenerateError(_))));
+
+    // The infallible path centers the lone point instead of producing NaN
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let (xs, ys) = generate_points(&mut rng.clone(), &ifs, 1, 32, 32);
+    assert_eq!((xs[0], ys[0]), (16.0, 16.0));
+    let image = render(rng, &ifs, &config);
+    assert_eq!(image.pixels().filter(|p| p.0 != [0, 0, 0]).count(), 1);
+}
+
+#[test]
+fn test_render_glow() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 50_000,
+        ..Config::default()
+    };
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let image = render_glow(rng, &ifs, &config);
+
+    // Dense regions saturate while sparse regions stay dim
+    let brightest = image
+        .pixels()
+        .map(|p| p.0.iter().max().copied().unwrap())
+        .max();
+    assert_eq!(brightest, Some(255));
+    assert!(image
+        .pixels()
+        .any(|p| p.0 != [0, 0, 0] && p.0.iter().all(|&c| c < 255)));
+}
+
+#[test]
+fn test_background_color() {
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 200,
+        background: [255, 255, 255],
+        ..Config::default()
+    };
+    let image = render_from_config(&config).unwrap();
+
+    // The border is never plotted, so it keeps the background color
+    assert_eq!(image.get_pixel(0, 0).0, [255, 255, 255]);
+    assert_eq!(image.get_pixel(31, 31).0, [255, 255, 255]);
+    assert!(image.pixels().any(|p| p.0 != [255, 255, 255]));
+
+    // The default background is black
+    let image = render_from_config(&Config {
+        background: Config::default().background,
+        ..config
+    })
+    .unwrap();
+    assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0]);
+}
+
+#[test]
+fn test_seeded_rng_matches_render_from_config() {
+    let config = Config {
+        height: 48,
+        width: 48,
+        npoints: 2000,
+        seed: 5,
+        ..Config::default()
+    };
+
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let image = render(rng, &ifs, &config);
+
+    assert_eq!(image, render_from_config(&config).unwrap());
+}
+
+#[test]
+fn test_point_size() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 300,
+        ..Config::default()
+    };
+    let thin = render_from_config(&config).unwrap();
+    let thick = render_from_config(&Config {
+        point_size: 6,
+        ..config
+    })
+    .unwrap();
+
+    // Thicker points cover more of the canvas, reaching the edges without
+    // writing outside the buffer
+    let covered = |img: &image::RgbImage| img.pixels().filter(|p| p.0 != [0, 0, 0]).count();
+    assert!(covered(&thick) > covered(&thin));
+    assert!((0..64).any(|y| thick.get_pixel(0, y).0 != [0, 0, 0]));
+}
+
+#[test]
+fn test_affine_homogeneous_round_trip() {
+    let mut rng = seeded_rng(3);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    for t in ifs.transforms() {
+        let m = t.to_homogeneous();
+        assert_eq!(m[(2, 0)], 0.0);
+        assert_eq!(m[(2, 1)], 0.0);
+        assert_eq!(m[(2, 2)], 1.0);
+
+        // Applying the homogeneous matrix matches apply
+        let p = nalgebra::Vector2::new(0.3, -0.7);
+        let q = m * p.push(1.0);
+        let expected = t.apply(&p);
+        assert!((q.x - expected.x).abs() < 1e-12 && (q.y - expected.y).abs() < 1e-12);
+
+        let back = Affine::from_homogeneous(&m);
+        assert_eq!(back.w, t.w);
+        assert_eq!(back.b, t.b);
+    }
+}
+
+#[test]
+fn test_render_trails() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 50,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let dots = render(rng.clone(), &ifs, &config);
+    let trails = render_trails(rng, &ifs, &config);
+
+    // Lines cover every plotted point and more
+    let covered = |img: &image::RgbImage| img.pixels().filter(|p| p.0 != [0, 0, 0]).count();
+    assert!(covered(&trails) > covered(&dots));
+    for (a, b) in dots.pixels().zip(trails.pixels()) {
+        if a.0 != [0, 0, 0] {
+            assert_eq!(a, b);
+        }
+    }
+}
+
+#[test]
+fn test_render_batch_to_dir_resumes() {
+    let out_dir = std::env::temp_dir().join(format!("rrl_batch_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out_dir);
+    let config = Config {
+        height: 16,
+        width: 16,
+        npoints: 200,
+        ..Config::default()
+    };
+
+    let summary = render_batch_to_dir(&config, &[1, 2], &out_dir).unwrap();
+    assert_eq!((summary.generated, summary.skipped), (2, 0));
+    assert!(batch_output_path(&out_dir, 1).exists());
+
+    // A second run only renders the new seed
+    let summary = render_batch_to_dir(&config, &[1, 2, 3], &out_dir).unwrap();
+    assert_eq!((summary.generated, summary.skipped), (1, 2));
+
+    // Degenerate attractors are skipped instead of aborting the batch
+    let single_point = Config {
+        npoints: 1,
+        max_retries: 0,
+        ..config.clone()
+    };
+    let summary = render_batch_to_dir(&single_point, &[4, 5], &out_dir).unwrap();
+    assert_eq!((summary.generated, summary.skipped), (0, 2));
+    assert!(!batch_output_path(&out_dir, 4).exists());
+
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
+
+#[test]
+fn test_tone_maps() {
+    let counts = [0, 1, 10, 100];
+    for op in [
+        ToneMap::Linear,
+        ToneMap::Log,
+        ToneMap::Reinhard,
+        ToneMap::Sqrt,
+    ] {
+        let v = tone_map(&counts, op);
+        assert_eq!(v[0], 0.0);
+        assert!((v[3] - 1.0).abs() < 1e-6);
+        assert!(v[1] < v[2] && v[2] < v[3]);
+    }
+
+    // Compressive operators lift faint pixels above linear mapping
+    let linear = tone_map(&counts, ToneMap::Linear);
+    assert!(tone_map(&counts, ToneMap::Log)[1] > linear[1]);
+    assert!(tone_map(&counts, ToneMap::Sqrt)[1] > linear[1]);
+    assert!(tone_map(&counts, ToneMap::Reinhard)[1] > linear[1]);
+}
+
+#[test]
+fn test_render_density() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 20_000,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let counts = density_buffer(&mut rng.clone(), &ifs, &config);
+    assert_eq!(counts.len(), 64 * 64);
+    assert_eq!(counts.iter().sum::<u32>(), 20_000);
+
+    let image = render_density(rng, &ifs, &config);
+    assert!(image.pixels().any(|p| p.0 != [0, 0, 0]));
+}
+
+#[test]
+fn test_render_gray_binary() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 20_000,
+        tone_map: ToneMap::Binary,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let counts = density_buffer(&mut rng.clone(), &ifs, &config);
+    let image = render_gray(rng, &ifs, &config);
+    assert_eq!(image.dimensions(), (64, 64));
+
+    // Every visited pixel is white and every other pixel black
+    for (pixel, &count) in image.pixels().zip(counts.iter()) {
+        assert_eq!(pixel.0[0], if count > 0 { 255 } else { 0 });
+    }
+}
+
+#[test]
+fn test_points_bytes_round_trip() {
+    let mut rng = seeded_rng(42);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let (xs, ys) = generate_points(&mut rng, &ifs, 100, 64, 64);
+
+    let bytes = points_to_bytes(&xs, &ys);
+    assert_eq!(bytes.len(), 100 * 16);
+    assert_eq!(&bytes[..8], &xs[0].to_le_bytes());
+    assert_eq!(&bytes[8..16], &ys[0].to_le_bytes());
+
+    let (xs2, ys2) = points_from_bytes(&bytes).unwrap();
+    assert_eq!(xs, xs2);
+    assert_eq!(ys, ys2);
+
+    assert!(points_from_bytes(&bytes[..15]).is_err());
+}
+
+#[test]
+fn test_flip_and_rotate() {
+    let config = Config {
+        height: 40,
+        width: 30,
+        npoints: 1000,
+        ..Config::default()
+    };
+    let image = render_from_config(&config).unwrap();
+
+    // flip_y in the config matches flipping the finished image
+    let flipped = render_from_config(&Config {
+        flip_y: true,
+        ..config.clone()
+    })
+    .unwrap();
+    let mut expected = image.clone();
+    flip_vertical(&mut expected);
+    assert_eq!(flipped, expected);
+
+    // flip_x mirrors columns
+    let mirrored = render_from_config(&Config {
+        flip_x: true,
+        ..config
+    })
+    .unwrap();
+    assert_eq!(mirrored.get_pixel(0, 7), image.get_pixel(29, 7));
+
+    let rotated = rotate_90(&image);
+    assert_eq!((rotated.width(), rotated.height()), (40, 30));
+}
+
+#[test]
+fn test_dither_quantization() {
+    // A smooth gradient spanning a few 8-bit levels
+    let values: Vec<f32> = (0..1000).map(|i| 100.0 + i as f32 * 0.004).collect();
+
+    let plain = quantize(&values, None);
+    let dithered = quantize(&values, Some(7));
+
+    assert_ne!(plain, dithered);
+    for (a, b) in plain.iter().zip(dithered.iter()) {
+        assert!((*a as i32 - *b as i32).abs() <= 1);
+    }
+
+    // The noise is reproducible for a given seed
+    assert_eq!(dithered, quantize(&values, Some(7)));
+
+    // The noise does not reuse the stream that chose the geometry
+    use rand::Rng;
+    let mut rng = seeded_rng(7);
+    let correlated: Vec<u8> = values
+        .iter()
+        .map(|&v| (v + rng.gen::<f32>() - 0.5).round().clamp(0.0, 255.0) as u8)
+        .collect();
+    assert_ne!(dithered, correlated);
+}
+
+#[test]
+fn test_dyn_ifs() {
+    let mut rng = seeded_rng(11);
+    let a = rand_sigma_factor_ifs(&mut rng);
+    let b = rand_sigma_factor_ifs(&mut rng);
+    let systems: Vec<Box<dyn DynIFS>> = vec![Box::new(a.clone()), Box::new(b)];
+
+    let point = nalgebra::Vector2::new(0.1, 0.2);
+    for ifs in &systems {
+        let p = ifs.apply_random_dyn(&mut rng, &point);
+        assert!(p.x.is_finite() && p.y.is_finite());
+    }
+
+    // The dynamic call draws the same randomness as the generic one
+    let expected = a.apply_random(&mut seeded_rng(5), &point);
+    assert_eq!(
+        systems[0].apply_random_dyn(&mut seeded_rng(5), &point),
+        expected
+    );
+}
+
+#[test]
+fn test_equalize_linearizes_cdf() {
+    // A strongly skewed histogram, plus unvisited pixels
+    let mut counts: Vec<u32> = (1..=1000).map(|i| i * i).collect();
+    counts.extend([0; 100]);
+    equalize(&mut counts);
+
+    // Unvisited pixels stay at zero
+    assert!(counts[1000..].iter().all(|&c| c == 0));
+
+    // The output CDF of visited pixels is approximately linear
+    let mut visited: Vec<u32> = counts[..1000].to_vec();
+    visited.sort_unstable();
+    for (i, &level) in visited.iter().enumerate() {
+        let cdf = (i + 1) as f64 / 1000.0;
+        let expected = cdf * EQUALIZE_LEVELS as f64;
+        assert!((level as f64 - expected).abs() <= 0.001 * EQUALIZE_LEVELS as f64);
+    }
+
+    // Equalization is accepted by the density renderer
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 5000,
+        equalize: true,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let image = render_density(rng, &ifs, &config);
+    assert!(image.pixels().any(|p| p.0 != [0, 0, 0]));
+}
+
+#[test]
+fn test_num_transforms() {
+    let mut rng = seeded_rng(42);
+    for n in 2..=6 {
+        let ifs = rand_sigma_factor_ifs_with_count(&mut rng, n);
+        assert_eq!(ifs.transforms().len(), n);
+    }
+
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 1000,
+        num_transforms: Some(3),
+        ..Config::default()
+    };
+    assert!(render_from_config(&config).is_ok());
+
+    let config = Config {
+        num_transforms: Some(1),
+        ..config
+    };
+    assert!(matches!(
+        render_from_config(&config),
+        Err(Error::ConfigError(_))
+    ));
+}
+
+#[test]
+fn test_generate_labeled_points() {
+    let mut rng = seeded_rng(42);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let labeled = generate_labeled_points(&mut rng.clone(), &ifs, 1000, 64, 64);
+    let (xs, ys) = generate_points(&mut rng.clone(), &ifs, 1000, 64, 64);
+
+    // The geometry matches the unlabeled orbit
+    assert_eq!(labeled.len(), 1000);
+    for (i, &(x, y, label)) in labeled.iter().enumerate() {
+        assert_eq!((x, y), (xs[i], ys[i]));
+        assert!(label < ifs.transforms().len());
+    }
+
+    // Every point is the image of the previous one under its labeled map
+    let mut point = nalgebra::Vector2::zeros();
+    let mut raw = Vec::new();
+    for &(_, _, label) in &labeled {
+        point = ifs.transforms()[label].apply(&point);
+        raw.push(point);
+    }
+    let (x_min, ..) = attractor_bounds(&mut rng, &ifs, 1000);
+    assert_eq!(x_min, raw.iter().map(|p| p.x).fold(f64::INFINITY, f64::min));
+}
+
+#[test]
+fn test_expand_template() {
+    let config = Config {
+        height: 200,
+        width: 300,
+        npoints: 1000,
+        seed: 7,
+        ..Config::default()
+    };
+
+    assert_eq!(
+        config.expand_template("fractal_{seed}_{width}x{height}_{npoints}.png"),
+        "fractal_7_300x200_1000.png"
+    );
+
+    // Unknown and unterminated placeholders are kept literally
+    assert_eq!(config.expand_template("a{foo}b{seed"), "a{foo}b{seed");
+    assert_eq!(config.expand_template("{{seed}}"), "{7}");
+}
+
+#[test]
+fn test_convex_hull() {
+    // A square with interior and edge points
+    let xs = [0.0, 1.0, 1.0, 0.0, 0.5, 0.5, 0.2];
+    let ys = [0.0, 0.0, 1.0, 1.0, 0.5, 0.0, 0.7];
+    let hull = convex_hull(&xs, &ys);
+    assert_eq!(hull, vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+
+    // Counterclockwise orientation has positive signed area
+    let area: f64 = (0..hull.len())
+        .map(|i| {
+            let (x0, y0) = hull[i];
+            let (x1, y1) = hull[(i + 1) % hull.len()];
+            x0 * y1 - x1 * y0
+        })
+        .sum();
+    assert!(area > 0.0);
+
+    // Degenerate inputs are handled gracefully
+    assert!(convex_hull(&[], &[]).is_empty());
+    assert_eq!(convex_hull(&[1.0, 1.0], &[2.0, 2.0]), vec![(1.0, 2.0)]);
+    assert_eq!(
+        convex_hull(&[0.0, 1.0, 2.0], &[0.0, 1.0, 2.0]),
+        vec![(0.0, 0.0), (2.0, 2.0)]
+    );
+
+    // Every attractor point lies inside the hull
+    let mut rng = seeded_rng(42);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let (xs, ys) = generate_points(&mut rng, &ifs, 2000, 100, 100);
+    let hull = convex_hull(&xs, &ys);
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        for i in 0..hull.len() {
+            let (x0, y0) = hull[i];
+            let (x1, y1) = hull[(i + 1) % hull.len()];
+            assert!((x1 - x0) * (y - y0) - (y1 - y0) * (x - x0) >= -1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_convex_hull_ignores_non_finite_points() {
+    // A diverged orbit mixes NaN and infinite coordinates into the cloud
+    let mut xs = vec![0.0, 1.0, 1.0, 0.0, 0.5];
+    let mut ys = vec![0.0, 0.0, 1.0, 1.0, 0.5];
+    for i in 0..100 {
+        xs.push(if i % 2 == 0 { f64::NAN } else { f64::INFINITY });
+        ys.push(i as f64);
+        xs.push(i as f64 / 100.0);
+        ys.push(f64::NAN);
+    }
+    let hull = convex_hull(&xs, &ys);
+    assert_eq!(hull.len(), 4);
+    assert!(hull.iter().all(|&(x, y)| x.is_finite() && y.is_finite()));
+
+    assert!(convex_hull(&[f64::NAN; 3], &[0.0, 1.0, 2.0]).is_empty());
+}
+
+#[test]
+fn test_region_of_interest() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 20_000,
+        ..Config::default()
+    };
+    let full = render_from_config(&config).unwrap();
+
+    // The identity region leaves the image unchanged
+    let same = render_from_config(&Config {
+        roi: Some((0.0, 1.0, 0.0, 1.0)),
+        ..config.clone()
+    })
+    .unwrap();
+    assert_eq!(full, same);
+
+    // Zooming into the lower-left quadrant maps its pixels to the full canvas
+    let zoomed = render_from_config(&Config {
+        roi: Some((0.0, 0.5, 0.0, 0.5)),
+        ..config.clone()
+    })
+    .unwrap();
+    for (x, y, p) in zoomed.enumerate_pixels() {
+        if p.0 != [0, 0, 0] {
+            assert_ne!(full.get_pixel(x / 2, y / 2).0, [0, 0, 0]);
+        }
+    }
+
+    // An empty region is rejected
+    let invalid = Config {
+        roi: Some((0.5, 0.5, 0.0, 1.0)),
+        ..config
+    };
+    assert!(matches!(
+        render_from_config(&invalid),
+        Err(Error::ConfigError(_))
+    ));
+}
+
+#[test]
+fn test_ifs_distance() {
+    let mut rng = seeded_rng(42);
+    let a = rand_sigma_factor_ifs_with_count(&mut rng, 3);
+    let b = rand_sigma_factor_ifs_with_count(&mut rng, 3);
+
+    assert_eq!(ifs_distance(&a, &a), 0.0);
+    assert!(ifs_distance(&a, &b) > 0.0);
+    assert!((ifs_distance(&a, &b) - ifs_distance(&b, &a)).abs() < 1e-12);
+
+    // Reordering the transforms does not change the distance
+    let mut transforms = a.transforms().to_vec();
+    let mut weights = a.weights().to_vec();
+    transforms.reverse();
+    weights.reverse();
+    let reordered = SigmaFactorIFS::new(transforms, weights);
+    assert!(ifs_distance(&a, &reordered) < 1e-12);
+
+    // Different transform counts are infinitely far apart
+    let c = rand_sigma_factor_ifs_with_count(&mut rng, 4);
+    assert_eq!(ifs_distance(&a, &c), f64::INFINITY);
+
+    // Large systems fall back to greedy matching
+    let big = rand_sigma_factor_ifs_with_count(&mut rng, 10);
+    assert_eq!(ifs_distance(&big, &big), 0.0);
+}
+
+#[test]
+fn test_render_multiseed() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 5000,
+        ..Config::default()
+    };
+
+    // A single seed reproduces the colors of the first palette entry
+    let single = render_multiseed(&config, &[3]);
+    assert!(single
+        .pixels()
+        .all(|p| p.0 == [0, 0, 0] || p.0 == [203, 60, 51]));
+
+    // Several seeds add their colors into one canvas
+    let multi = render_multiseed(&config, &[3, 4, 5]);
+    let colors: std::collections::HashSet<[u8; 3]> = multi.pixels().map(|p| p.0).collect();
+    assert!(colors.len() > 3);
+    assert_eq!(multi, render_multiseed(&config, &[3, 4, 5]));
+
+    // A single seed is framed like render, region of interest included
+    let zoomed = Config {
+        roi: Some((0.25, 0.75, 0.0, 0.5)),
+        ..config.clone()
+    };
+    let mut rng = seeded_rng(3);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let expected = render(
+        rng,
+        &ifs,
+        &Config {
+            seed: 3,
+            ..zoomed.clone()
+        },
+    );
+    let single = render_multiseed(&zoomed, &[3]);
+    assert_ne!(single, render_multiseed(&config, &[3]));
+    for (a, b) in single.pixels().zip(expected.pixels()) {
+        assert_eq!(a.0 == [0, 0, 0], b.0 == [0, 0, 0]);
+    }
+}
+
+#[test]
+fn test_rng_state_round_trip() {
+    use rand::Rng;
+
+    // An uninterrupted stream
+    let mut rng = seeded_rng(42);
+    let full: Vec<u64> = (0..200).map(|_| rng.gen()).collect();
+
+    // The same stream, snapshotted and restored halfway
+    let mut rng = seeded_rng(42);
+    let mut split: Vec<u64> = (0..100).map(|_| rng.gen()).collect();
+    let bytes = rng_state_to_bytes(&rng);
+    let mut restored = rng_from_bytes(bytes);
+    split.extend((0..100).map(|_| restored.gen::<u64>()));
+
+    assert_eq!(full, split);
+
+    // The exported bytes are fixed, so checkpoints survive dependency updates
+    const GOLDEN: [u8; 32] = [
+        149, 110, 235, 47, 38, 50, 215, 189, 3, 241, 102, 178, 51, 227, 239, 40, 82, 159, 15, 19,
+        87, 103, 82, 71, 148, 227, 74, 14, 255, 225, 28, 88,
+    ];
+    assert_eq!(rng_state_to_bytes(&seeded_rng(42)), GOLDEN);
+    assert_eq!(rng_from_bytes(GOLDEN).gen::<u64>(), 15021278609987233951);
+}
+
+#[test]
+fn test_resize() {
+    let mut img = image::RgbImage::from_pixel(64, 48, image::Rgb([10, 20, 30]));
+    img.put_pixel(0, 0, image::Rgb([255, 255, 255]));
+
+    let small = resize(&img, 16, 12, FilterKind::Lanczos3);
+    assert_eq!(small.dimensions(), (16, 12));
+
+    // A uniform region keeps its color under every filter
+    let nearest = resize(&img, 32, 24, FilterKind::Nearest);
+    assert_eq!(nearest.get_pixel(31, 23).0, [10, 20, 30]);
+    let gaussian = resize(&img, 32, 24, FilterKind::Gaussian);
+    assert_eq!(gaussian.get_pixel(16, 12).0, [10, 20, 30]);
+}
+
+#[test]
+fn test_target_coverage() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 2_000,
+        ..Config::default()
+    };
+    let covered = |img: &image::RgbImage| {
+        img.pixels().filter(|p| p.0 != [0, 0, 0]).count() as f64 / (64.0 * 64.0)
+    };
+    let base = covered(&render_from_config(&config).unwrap());
+
+    // A higher target thickens the points
+    let target = (base * 3.0).min(0.9);
+    let config = Config {
+        target_coverage: Some(target),
+        ..config
+    };
+    let exposed = covered(&render_from_config(&config).unwrap());
+    assert!(exposed > base);
+    assert!((exposed - target).abs() < (base - target).abs());
+
+    // A single point can never cover more than the largest square
+    let size = auto_point_size(&[32.0], &[32.0], 64, 64, 1.0);
+    assert_eq!(size, MAX_AUTO_POINT_SIZE);
+
+    let invalid = Config {
+        target_coverage: Some(0.0),
+        ..Config::default()
+    };
+    assert!(invalid.validate().is_err());
+}
+
+#[test]
+fn test_max_retries() {
+    // A single point collapses for every seed, so retrying still fails
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 1,
+        max_retries: 3,
+        ..Config::default()
+    };
+    let result = render_from_config(&config);
+    assert!(matches!(result, Err(Error::DegenerateError(_))));
+
+    // A healthy attractor is rendered on the first attempt
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 1_000,
+        max_retries: 3,
+        ..Config::default()
+    };
+    let retried = render_from_config(&config).unwrap();
+    let plain = render_from_config(&Config {
+        max_retries: 0,
+        ..config
+    })
+    .unwrap();
+    assert_eq!(retried, plain);
+}
+
+#[test]
+fn test_contraction_ratios() {
+    let b = nalgebra::Vector2::new(0.0, 0.0);
+    let ifs = SigmaFactorIFS::new(
+        vec![
+            Affine::new(nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.25), b),
+            Affine::new(nalgebra::Matrix2::new(0.0, -0.8, 0.3, 0.0), b),
+        ],
+        vec![0.5, 0.5],
+    );
+
+    let ratios = ifs.contraction_ratios();
+    assert_eq!(ratios.len(), 2);
+    assert!((ratios[0] - 0.5).abs() < 1e-12);
+    assert!((ratios[1] - 0.8).abs() < 1e-12);
+}
+
+#[test]
+fn test_temporal_color_scheme() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 5_000,
+        color_scheme: ColorScheme::Temporal,
+        ..Config::default()
+    };
+    let image = render_from_config(&config).unwrap();
+
+    // The fade produces many distinct colors instead of a single one
+    let mut colors: Vec<[u8; 3]> = image
+        .pixels()
+        .filter(|p| p.0 != [0, 0, 0])
+        .map(|p| p.0)
+        .collect();
+    colors.sort_unstable();
+    colors.dedup();
+    assert!(colors.len() > 10);
+
+    // The same pixels are covered as with a single color
+    let single = render_from_config(&Config {
+        color_scheme: ColorScheme::Single,
+        ..config
+    })
+    .unwrap();
+    for (a, b) in image.pixels().zip(single.pixels()) {
+        assert_eq!(a.0 == [0, 0, 0], b.0 == [0, 0, 0]);
+    }
+}
+
+#[test]
+fn test_sigma_factor_ifs_try_new() {
+    let t = Affine::new(
+        nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5),
+        nalgebra::Vector2::new(0.0, 0.0),
+    );
+
+    assert!(SigmaFactorIFS::try_new(vec![t.clone(), t.clone()], vec![0.3, 0.7]).is_ok());
+
+    // Mismatched lengths, empty input and invalid weights are rejected
+    let invalid = [
+        (vec![t.clone(), t.clone()], vec![1.0]),
+        (vec![], vec![]),
+        (vec![t.clone(), t.clone()], vec![0.0, 0.0]),
+        (vec![t.clone(), t.clone()], vec![-1.0, 2.0]),
+        (vec![t.clone(), t.clone()], vec![f64::NAN, 1.0]),
+    ];
+    for (transforms, weights) in invalid {
+        let result = SigmaFactorIFS::try_new(transforms, weights);
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+    }
+}
+
+#[test]
+fn test_apply_random_respects_cached_weights() {
+    let t = Affine::new(
+        nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5),
+        nalgebra::Vector2::new(0.0, 0.0),
+    );
+    let ifs = SigmaFactorIFS::try_new(vec![t.clone(), t], vec![0.0, 1.0]).unwrap();
+
+    // A transform with zero weight is never selected
+    let mut rng = seeded_rng(3);
+    let point = nalgebra::Vector2::new(1.0, 1.0);
+    for _ in 0..1_000 {
+        assert_eq!(ifs.apply_random_indexed(&mut rng, &point).0, 1);
+    }
+}
+
+#[test]
+fn test_render_grid() {
+    let config = Config {
+        height: 32,
+        width: 48,
+        npoints: 2_000,
+        ..Config::default()
+    };
+    let grid = render_grid(&config, 2, 3, 100).unwrap();
+    assert_eq!(grid.dimensions(), (3 * 48, 2 * 32));
+
+    // Each tile matches a standalone rendering with its own seed
+    let tile = image::imageops::crop_imm(&grid, 48, 32, 48, 32).to_image();
+    let mut rng = seeded_rng(104);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let expected = render(
+        rng,
+        &ifs,
+        &Config {
+            seed: 104,
+            ..config
+        },
+    );
+    assert_eq!(tile, expected);
+}
+
+#[test]
+fn test_clamp_radius() {
+    // An expanding map that pushes the orbit away from the origin
+    let t = Affine::new(
+        nalgebra::Matrix2::new(2.0, 0.0, 0.0, 2.0),
+        nalgebra::Vector2::new(1.0, 0.0),
+    );
+    let ifs = SigmaFactorIFS::new(vec![t], vec![1.0]);
+
+    let mut rng = seeded_rng(1);
+    let (xs, _, clamps) = generate_clamped_points(&mut rng, &ifs, 100, 64, 64, Some(10.0));
+    assert_eq!(xs.len(), 100);
+    assert!(clamps > 90);
+
+    // Without a radius the orbit is never reset
+    let (_, _, clamps) = generate_clamped_points(&mut rng, &ifs, 100, 64, 64, None);
+    assert_eq!(clamps, 0);
+
+    let invalid = Config {
+        clamp_radius: Some(-1.0),
+        ..Config::default()
+    };
+    assert!(invalid.validate().is_err());
+}
+
+#[test]
+fn test_julia_gradient() {
+    use rust_random_logo::core::utils::{JULIA_BLUE, JULIA_GREEN, JULIA_PURPLE, JULIA_RED};
+
+    // The gradient passes through every Julia color and wraps around
+    assert_eq!(julia_gradient(0.0), JULIA_RED);
+    assert_eq!(julia_gradient(0.25), JULIA_GREEN);
+    assert_eq!(julia_gradient(0.5), JULIA_BLUE);
+    assert_eq!(julia_gradient(0.75), JULIA_PURPLE);
+    assert_eq!(julia_gradient(1.0), JULIA_RED);
+
+    // Halfway between two stops lies between their channels
+    let mid = julia_gradient(0.125);
+    for c in 0..3 {
+        let (a, b) = (
+            JULIA_RED[c].min(JULIA_GREEN[c]),
+            JULIA_RED[c].max(JULIA_GREEN[c]),
+        );
+        assert!(a <= mid[c] && mid[c] <= b);
+    }
+}
+
+#[test]
+fn test_jitter() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 5_000,
+        ..Config::default()
+    };
+    let jittered = Config {
+        jitter: true,
+        ..config.clone()
+    };
+
+    // Jitter is reproducible and changes the rasterization
+    let a = render_from_config(&jittered).unwrap();
+    assert_eq!(a, render_from_config(&jittered).unwrap());
+    let plain = render_from_config(&config).unwrap();
+    assert_ne!(a, plain);
+
+    // The geometry and color are unchanged, so both share the same color
+    let color = |img: &image::RgbImage| *img.pixels().find(|p| p.0 != [0, 0, 0]).unwrap();
+    assert_eq!(color(&a), color(&plain));
+}
+
+#[test]
+fn test_luminance_histogram() {
+    let mut img = image::RgbImage::new(4, 4);
+    img.put_pixel(0, 0, image::Rgb([255, 255, 255]));
+    img.put_pixel(1, 0, image::Rgb([0, 255, 0]));
+
+    let histogram = luminance_histogram(&img);
+    assert_eq!(histogram.iter().sum::<u32>(), 16);
+    assert_eq!(histogram[0], 14);
+    assert_eq!(histogram[255], 1);
+    assert_eq!(histogram[182], 1);
+}
+
+#[test]
+fn test_config_many_from_file() {
+    let path = std::env::temp_dir().join(format!("rrl_jobs_{}.toml", std::process::id()));
+    let job = |seed: u64| {
+        format!(
+            "[[job]]\nheight = 32\nwidth = 48\nnpoints = 100\nifs_name = \"SigmaFactorIFS\"\nndims = 2\nrng_name = \"Xoshiro256PlusPlus\"\nseed = {}\n",
+            seed
+        )
+    };
+    std::fs::write(
+        &path,
+        format!("{}\n{}\ntone_map = \"Sqrt\"\n", job(1), job(2)),
+    )
+    .unwrap();
+
+    let configs = Config::many_from_file(&path).unwrap();
+    assert_eq!(configs.len(), 2);
+    assert_eq!((configs[0].seed, configs[1].seed), (1, 2));
+    assert_eq!(configs[1].width, 48);
+    assert_eq!(configs[1].tone_map, ToneMap::Sqrt);
+
+    // A plain configuration file still loads as a single config
+    let config = Config::from_file("examples/config.toml").unwrap();
+    assert_eq!(config.ifs_name, "SigmaFactorIFS");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_render_smoke() {
+    let image = render_smoke().unwrap();
+    assert_eq!(image.dimensions(), (32, 32));
+    assert!(image.pixels().any(|p| p.0 != [0, 0, 0]));
+}
+
+#[test]
+fn test_detect_symmetry() {
+    use rand::Rng;
+
+    // A random cloud in the right half, mirrored into the left half
+    let mut rng = seeded_rng(5);
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    for _ in 0..2_000 {
+        let x: f64 = rng.gen_range(0.0..1.0);
+        let y: f64 = rng.gen_range(0.0..x.max(0.01));
+        xs.extend([x, -x]);
+        ys.extend([y, y]);
+    }
+
+    let report = detect_symmetry(&xs, &ys);
+    assert!(report.flip_x);
+    assert!(report.flip_x_score > 0.99);
+    assert!(!report.flip_y);
+    assert!(!report.rotation);
+
+    // Degenerate clouds have no detected symmetry
+    let report = detect_symmetry(&[1.0, 1.0], &[2.0, 3.0]);
+    assert!(!report.flip_x && !report.flip_y && !report.rotation);
+}
+
+#[test]
+fn test_fixed_bounds() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 5_000,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let bounds = attractor_bounds(&mut rng.clone(), &ifs, config.npoints);
+
+    // Fixing the bounds to the orbit's own bounds reproduces the default
+    let fixed = Config {
+        fixed_bounds: Some(bounds),
+        ..config.clone()
+    };
+    assert_eq!(
+        render(rng.clone(), &ifs, &config),
+        render(rng.clone(), &ifs, &fixed)
+    );
+
+    // Fixing them to the left half clips the right half of the attractor
+    let (x_min, x_max, y_min, y_max) = bounds;
+    let half = Config {
+        fixed_bounds: Some((x_min, 0.5 * (x_min + x_max), y_min, y_max)),
+        ..config.clone()
+    };
+    let covered = |img: &image::RgbImage| img.pixels().filter(|p| p.0 != [0, 0, 0]).count();
+    assert!(covered(&render(rng, &ifs, &half)) > 0);
+
+    let invalid = Config {
+        fixed_bounds: Some((1.0, 0.0, 0.0, 1.0)),
+        ..config
+    };
+    assert!(invalid.validate().is_err());
+}
+
+#[test]
+fn test_render_density_16() {
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 5_000,
+        bit_depth: 16,
+        ..Config::default()
+    };
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    // The 16-bit image agrees with the 8-bit one up to quantization
+    let deep = render_density_16(rng.clone(), &ifs, &config);
+    let shallow = render_density(rng.clone(), &ifs, &config);
+    for (a, b) in deep.pixels().zip(shallow.pixels()) {
+        for c in 0..3 {
+            assert!((a[c] as f64 / 257.0 - b[c] as f64).abs() <= 0.5 + 1e-9);
+        }
+    }
+
+    // Saved as a 16-bit PNG
+    let path = std::env::temp_dir().join(format!("rrl_deep_{}.png", std::process::id()));
+    save_density(rng, &ifs, &config, &path).unwrap();
+    let loaded = image::open(&path).unwrap();
+    assert_eq!(loaded.color(), image::ColorType::Rgb16);
+    std::fs::remove_file(&path).unwrap();
+
+    // Only 8 and 16 bits are accepted
+    let invalid = Config {
+        bit_depth: 12,
+        ..config
+    };
+    assert!(invalid.validate().is_err());
+}
+
+#[test]
+fn test_shuffle_draw() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 5_000,
+        color_scheme: ColorScheme::Temporal,
+        shuffle_draw: true,
+        ..Config::default()
+    };
+
+    // The shuffled order is reproducible
+    let shuffled = render_from_config(&config).unwrap();
+    assert_eq!(shuffled, render_from_config(&config).unwrap());
+
+    // Colors of contested pixels change, but the covered pixels do not
+    let ordered = render_from_config(&Config {
+        shuffle_draw: false,
+        ..config
+    })
+    .unwrap();
+    assert_ne!(shuffled, ordered);
+    for (a, b) in shuffled.pixels().zip(ordered.pixels()) {
+        assert_eq!(a.0 == [0, 0, 0], b.0 == [0, 0, 0]);
+    }
+}
+
+#[test]
+fn test_affine_apply_many() {
+    let mut rng = seeded_rng(8);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let t = &ifs.transforms()[0];
+
+    let points: Vec<_> = (0..50)
+        .map(|i| nalgebra::Vector2::new(i as f64 * 0.1, 1.0 - i as f64 * 0.03))
+        .collect();
+    let mut out = vec![nalgebra::Vector2::zeros(); points.len()];
+    t.apply_many(&points, &mut out);
+
+    for (p, o) in points.iter().zip(out.iter()) {
+        assert!((t.apply(p) - o).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_generate_with_manifest() {
+    let config = Config {
+        height: 48,
+        width: 48,
+        npoints: 2_000,
+        num_transforms: Some(3),
+        ..Config::default()
+    };
+    let (image, manifest) = generate_with_manifest(&config).unwrap();
+
+    // The image is the one render_from_config produces
+    assert_eq!(image, render_from_config(&config).unwrap());
+    assert_eq!(manifest.seed, config.seed);
+    assert_eq!(manifest.transforms.len(), 3);
+    assert_eq!(manifest.coverage, coverage(&image, config.background));
+    assert!(manifest.coverage > 0.0);
+
+    // The IFS is recovered from the seed
+    let mut rng = seeded_rng(manifest.seed);
+    let ifs = rand_sigma_factor_ifs_with_count(&mut rng, 3);
+    for (record, t) in manifest.transforms.iter().zip(ifs.transforms().iter()) {
+        assert_eq!(record.w, [[t.w.m11, t.w.m12], [t.w.m21, t.w.m22]]);
+        assert_eq!(record.contraction_ratio, t.contraction_ratio());
+    }
+
+    // The manifest round-trips through JSON
+    let json = manifest.to_json().unwrap();
+    let parsed: rust_random_logo::Manifest = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.transforms, manifest.transforms);
+}
+
+#[test]
+fn test_quantize_to_palette() {
+    // A horizontal gray gradient from black to white
+    let img = image::RgbImage::from_fn(256, 1, |x, _| image::Rgb([x as u8; 3]));
+    let black = image::Rgb([0, 0, 0]);
+    let white = image::Rgb([255, 255, 255]);
+
+    let quantized = quantize_to_palette(&img, &[black, white], false);
+    assert_eq!(*quantized.get_pixel(0, 0), black);
+    assert_eq!(*quantized.get_pixel(255, 0), white);
+
+    // The threshold lies halfway between the two colors
+    assert_eq!(*quantized.get_pixel(127, 0), black);
+    assert_eq!(*quantized.get_pixel(128, 0), white);
+}
+
+#[test]
+fn test_explicit_transforms() {
+    // The Sierpinski triangle
+    let toml = r#"
+        height = 64
+        width = 64
+        npoints = 20000
+        ifs_name = "SigmaFactorIFS"
+        ndims = 2
+        rng_name = "Xoshiro256PlusPlus"
+        seed = 1
+        transforms = [
+            [0.5, 0.0, 0.0, 0.5, 0.0, 0.0],
+            [0.5, 0.0, 0.0, 0.5, 0.5, 0.0],
+            [0.5, 0.0, 0.0, 0.5, 0.25, 0.5],
+        ]
+        weights = [1.0, 1.0, 1.0]
+    "#;
+    let config = Config::from_reader(toml.as_bytes()).unwrap();
+    let image = render_from_config(&config).unwrap();
+
+    // The central hole of the triangle stays empty
+    assert!(image.pixels().any(|p| p.0 != [0, 0, 0]));
+    assert_eq!(image.get_pixel(32, 23).0, [0, 0, 0]);
+
+    // The coefficients map to the expected matrix and translation
+    let ifs = ifs_from_coefficients(&[[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]], None).unwrap();
+    let p = ifs.transforms()[0].apply(&nalgebra::Vector2::new(1.0, 1.0));
+    assert_eq!((p.x, p.y), (8.0, 13.0));
+
+    // Mismatched weights are rejected
+    let invalid = Config {
+        weights: Some(vec![1.0]),
+        ..config
+    };
+    assert!(matches!(invalid.validate(), Err(Error::ConfigError(_))));
+}
+
+#[test]
+fn test_normalize_points_matches_separate_passes() {
+    let mut rng = seeded_rng(21);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let (mut xs, mut ys) = generate_points(&mut rng, &ifs, 10_000, 1, 1);
+    for (i, x) in xs.iter_mut().enumerate() {
+        *x = *x * 3.0 - 1.0 + i as f64 * 1e-4;
+    }
+
+    // Reference bounds computed with one pass per extremum
+    let x_min = xs.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let x_max = xs.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let y_min = ys.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let y_max = ys.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    assert_eq!(point_bounds(&xs, &ys), (x_min, x_max, y_min, y_max));
+
+    let expected: Vec<f64> = xs
+        .iter()
+        .map(|x| 374.0 * (x - x_min) / (x_max - x_min) + 5.0)
+        .collect();
+    normalize_points(&mut xs, &mut ys, 384, 384);
+    assert_eq!(xs, expected);
+}
+
+#[test]
+fn test_hsv_to_rgb() {
+    assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0).0, [255, 0, 0]);
+    assert_eq!(hsv_to_rgb(120.0, 1.0, 1.0).0, [0, 255, 0]);
+    assert_eq!(hsv_to_rgb(240.0, 1.0, 1.0).0, [0, 0, 255]);
+    assert_eq!(hsv_to_rgb(60.0, 1.0, 1.0).0, [255, 255, 0]);
+    assert_eq!(hsv_to_rgb(360.0, 1.0, 1.0).0, [255, 0, 0]);
+    assert_eq!(hsv_to_rgb(-120.0, 1.0, 1.0).0, [0, 0, 255]);
+
+    // Zero saturation gives grays and zero value black
+    assert_eq!(hsv_to_rgb(200.0, 0.0, 0.5).0, [128, 128, 128]);
+    assert_eq!(hsv_to_rgb(200.0, 1.0, 0.0).0, [0, 0, 0]);
+}
+
+#[test]
+fn test_angular_hue_color_scheme() {
+    let config = Config {
+        height: 64,
+        width: 64,
+        npoints: 5_000,
+        color_scheme: ColorScheme::AngularHue,
+        ..Config::default()
+    };
+    let image = render_from_config(&config).unwrap();
+
+    // Points around the centroid span many hues
+    let mut colors: Vec<[u8; 3]> = image
+        .pixels()
+        .filter(|p| p.0 != [0, 0, 0])
+        .map(|p| p.0)
+        .collect();
+    colors.sort_unstable();
+    colors.dedup();
+    assert!(colors.len() > 20);
+}
+
+#[test]
+fn test_generate_points_multistart() {
+    let mut rng = seeded_rng(4);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    let (xs, ys) = generate_points_multistart(&mut rng, &ifs, 500, 8, 64, 64);
+    assert_eq!((xs.len(), ys.len()), (4_000, 4_000));
+
+    // The merged cloud is normalized to the canvas with its margin
+    let (x_min, x_max, y_min, y_max) = point_bounds(&xs, &ys);
+    assert!((x_min - 5.0).abs() < 1e-9 && (x_max - 59.0).abs() < 1e-9);
+    assert!((y_min - 5.0).abs() < 1e-9 && (y_max - 59.0).abs() < 1e-9);
+
+    // Generation is reproducible
+    let mut rng = seeded_rng(4);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    assert_eq!(
+        generate_points_multistart(&mut rng, &ifs, 500, 8, 64, 64),
+        (xs, ys)
+    );
+}
+
+#[test]
+fn test_ifs_num_transforms() {
+    let mut rng = seeded_rng(13);
+    let ifs = rand_sigma_factor_ifs_with_count(&mut rng, 3);
+    assert_eq!(IFS::num_transforms(&ifs), 3);
+
+    // Also available through generic and dynamic code
+    fn count<T: IFS>(ifs: &T) -> usize {
+        ifs.num_transforms()
+    }
+    assert_eq!(count(&ifs), 3);
+    let boxed: Box<dyn DynIFS> = Box::new(ifs);
+    assert_eq!(boxed.num_transforms_dyn(), 3);
+}
+
+#[test]
+fn test_quantize_to_palette_dithering() {
+    // A flat gray block quantized to black and white
+    let img = image::RgbImage::from_pixel(32, 32, image::Rgb([64, 64, 64]));
+    let palette = [image::Rgb([0, 0, 0]), image::Rgb([255, 255, 255])];
+
+    // Without dithering the block collapses to black
+    let plain = quantize_to_palette(&img, &palette, false);
+    assert!(plain.pixels().all(|p| p.0 == [0, 0, 0]));
+
+    // Error diffusion preserves the average color
+    let dithered = quantize_to_palette(&img, &palette, true);
+    let mean = dithered.pixels().map(|p| p[0] as f64).sum::<f64>() / (32.0 * 32.0);
+    assert!((mean - 64.0).abs() < 4.0);
+
+    // Dithering is deterministic
+    assert_eq!(dithered, quantize_to_palette(&img, &palette, true));
+}
+
+#[test]
+fn test_plot_stride() {
+    let mut config = Config::new();
+    config.height = 64;
+    config.width = 64;
+    config.npoints = 20000;
+
+    let count = |img: &image::RgbImage| img.pixels().filter(|p| p.0 != [0, 0, 0]).count();
+    let full = render(
+        seeded_rng(3),
+        &rand_sigma_factor_ifs(&mut seeded_rng(3)),
+        &config,
+    );
+
+    // Thinning draws a subset of the pixels of the full orbit
+    config.plot_stride = 7;
+    let sparse = render(
+        seeded_rng(3),
+        &rand_sigma_factor_ifs(&mut seeded_rng(3)),
+        &config,
+    );
+    assert!(count(&sparse) > 0);
+    assert!(count(&sparse) < count(&full));
+    for (a, b) in sparse.pixels().zip(full.pixels()) {
+        if a.0 != [0, 0, 0] {
+            assert_ne!(b.0, [0, 0, 0]);
+        }
+    }
+
+    config.plot_stride = 0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_attractor_stats() {
+    let xs = [1.0, 3.0, 1.0, 3.0];
+    let ys = [-2.0, -2.0, 4.0, 4.0];
+    let stats = attractor_stats(&xs, &ys);
+    assert_eq!(stats.centroid, (2.0, 1.0));
+    assert_eq!(stats.std_dev, (1.0, 3.0));
+    assert_eq!(stats.bounds, point_bounds(&xs, &ys));
+
+    // Precision holds far from the origin
+    let xs: Vec<f64> = (0..1000).map(|i| 1e9 + (i % 2) as f64).collect();
+    let ys = vec![0.0; 1000];
+    let stats = attractor_stats(&xs, &ys);
+    assert!((stats.std_dev.0 - 0.5).abs() < 1e-6);
+    assert_eq!(stats.std_dev.1, 0.0);
+
+    let empty = attractor_stats(&[], &[]);
+    assert_eq!(empty.centroid, (0.0, 0.0));
+    assert_eq!(empty.std_dev, (0.0, 0.0));
+}
+
+#[test]
+fn test_render_overlay() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 5000;
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(4));
+    let fractal = render(seeded_rng(4), &ifs, &config);
+
+    // Existing content shows through the background of the fractal
+    let gray = image::Rgb([128, 128, 128]);
+    let mut base = image::RgbImage::from_pixel(48, 40, gray);
+    render_overlay(&mut base, seeded_rng(4), &ifs, &config, (30, 20)).unwrap();
+    for (x, y, pixel) in base.enumerate_pixels() {
+        let inside = (30..48).contains(&x) && (20..40).contains(&y);
+        let expected = if inside && fractal.get_pixel(x - 30, y - 20).0 != [0, 0, 0] {
+            *fractal.get_pixel(x - 30, y - 20)
+        } else {
+            gray
+        };
+        assert_eq!(*pixel, expected);
+    }
+
+    // An origin outside the image draws nothing
+    let mut base = image::RgbImage::from_pixel(8, 8, gray);
+    render_overlay(&mut base, seeded_rng(4), &ifs, &config, (8, 0)).unwrap();
+    assert!(base.pixels().all(|p| *p == gray));
+}
+
+#[test]
+fn test_try_render() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 1000;
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(5));
+
+    // Matches the infallible path on valid input
+    let img = try_render(seeded_rng(5), &ifs, &config).unwrap();
+    assert_eq!(img, render(seeded_rng(5), &ifs, &config));
+
+    // Invalid configuration
+    config.plot_stride = 0;
+    assert!(matches!(
+        try_render(seeded_rng(5), &ifs, &config),
+        Err(Error::ConfigError(_))
+    ));
+    config.plot_stride = 1;
+
+    // Malformed IFS
+    let broken = SigmaFactorIFS::new(
+        vec![Affine::new(
+            nalgebra::Matrix2::new(f64::NAN, 0.0, 0.0, 0.5),
+            nalgebra::Vector2::new(0.0, 0.0),
+        )],
+        vec![1.0],
+    );
+    assert!(matches!(
+        try_render(seeded_rng(5), &broken, &config),
+        Err(Error::IfsError(_))
+    ));
+
+    // Diverging orbit
+    let expanding = SigmaFactorIFS::new(
+        vec![Affine::new(
+            nalgebra::Matrix2::new(1e200, 0.0, 0.0, 1e200),
+            nalgebra::Vector2::new(1.0, 1.0),
+        )],
+        vec![1.0],
+    );
+    assert!(matches!(
+        try_render(seeded_rng(5), &expanding, &config),
+        Err(Error::DegenerateError(_))
+    ));
+}
+
+#[test]
+fn test_skeleton_to_svg() {
+    // Sierpinski triangle: three half-size copies of the unit square
+    let half = nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5);
+    let ifs = SigmaFactorIFS::new(
+        vec![
+            Affine::new(half, nalgebra::Vector2::new(0.0, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.5, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.25, 0.5)),
+        ],
+        vec![1.0, 1.0, 1.0],
+    );
+    let mut config = Config::new();
+    config.stroke_width = 2.5;
+
+    let svg = skeleton_to_svg(&ifs, &config);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("viewBox=\"0 0 1 1\""));
+    assert_eq!(svg.matches("<polygon").count(), 3);
+    assert_eq!(svg.matches("stroke-width=\"2.5\"").count(), 3);
+    assert!(svg.contains("points=\"0.5,0 1,0 1,0.5 0.5,0.5\""));
+    assert!(svg.contains("stroke=\"rgb(203,60,51)\""));
+    assert!(svg.contains("stroke=\"rgb(56,152,38)\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    config.stroke_width = 0.0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_render_deterministic() {
+    let half = nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5);
+    let ifs = SigmaFactorIFS::new(
+        vec![
+            Affine::new(half, nalgebra::Vector2::new(0.0, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.5, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.25, 0.5)),
+        ],
+        vec![1.0, 1.0, 1.0],
+    );
+    let mut config = Config::new();
+    config.height = 64;
+    config.width = 64;
+
+    // 3^6 points are plotted without randomness
+    let img = render_deterministic(&ifs, &config, 6);
+    assert_eq!(img, render_deterministic(&ifs, &config, 6));
+    let lit = img.pixels().filter(|p| p.0 != [0, 0, 0]).count();
+    assert!(lit > 200 && lit <= 729);
+
+    // The central hole of the Sierpinski triangle stays empty
+    assert_eq!(img.get_pixel(32, 23).0, [0, 0, 0]);
+
+    // Excessive depths are capped rather than exhausting memory
+    assert!(3usize.pow(14) > MAX_DETERMINISTIC_POINTS);
+    let capped = render_deterministic(&ifs, &config, 1000);
+    assert!(capped.pixels().any(|p| p.0 != [0, 0, 0]));
+}
+
+#[test]
+fn test_sample_attractor_point() {
+    // The attractor of these maps is the unit square
+    let half = nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5);
+    let ifs = SigmaFactorIFS::new(
+        vec![
+            Affine::new(half, nalgebra::Vector2::new(0.0, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.5, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.0, 0.5)),
+            Affine::new(half, nalgebra::Vector2::new(0.5, 0.5)),
+        ],
+        vec![1.0; 4],
+    );
+
+    // Samples spread over the whole attractor
+    let mut rng = seeded_rng(8);
+    let samples: Vec<_> = (0..2000)
+        .map(|_| sample_attractor_point(&mut rng, &ifs, 50))
+        .collect();
+    assert!(samples
+        .iter()
+        .all(|p| (0.0..=1.0).contains(&p.x) && (0.0..=1.0).contains(&p.y)));
+    let mean_x = samples.iter().map(|p| p.x).sum::<f64>() / samples.len() as f64;
+    assert!((mean_x - 0.5).abs() < 0.05);
+
+    // No steps leaves the starting point
+    assert_eq!(
+        sample_attractor_point(&mut rng, &ifs, 0),
+        nalgebra::Vector2::zeros()
+    );
+}
+
+#[test]
+fn test_similarity_dimension() {
+    let similitudes = |r: f64, n: usize| {
+        SigmaFactorIFS::new(
+            (0..n)
+                .map(|i| {
+                    Affine::new(
+                        nalgebra::Matrix2::new(r, 0.0, 0.0, r),
+                        nalgebra::Vector2::new(i as f64, 0.0),
+                    )
+                })
+                .collect(),
+            vec![1.0; n],
+        )
+    };
+
+    // Sierpinski triangle: log 3 / log 2
+    let sierpinski = similarity_dimension(&similitudes(0.5, 3));
+    assert!((sierpinski - 3f64.ln() / 2f64.ln()).abs() < 1e-9);
+
+    // Cantor set: log 2 / log 3
+    let cantor = similarity_dimension(&similitudes(1.0 / 3.0, 2));
+    assert!((cantor - 2f64.ln() / 3f64.ln()).abs() < 1e-9);
+
+    // A single map has a point attractor
+    assert_eq!(similarity_dimension(&similitudes(0.5, 1)), 0.0);
+
+    // Expanding maps have no solution
+    assert!(similarity_dimension(&similitudes(1.5, 2)).is_infinite());
+}
+
+#[test]
+fn test_write_points_csv() {
+    let xs = [0.1, -2.5, 1e-300];
+    let ys = [1.0 / 3.0, 4.0, f64::MAX];
+    let mut out = Vec::new();
+    write_points_csv(&xs, &ys, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    // Header row, then full-precision rows
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("x,y"));
+    for (line, (x, y)) in lines.zip(xs.iter().zip(ys.iter())) {
+        let (a, b) = line.split_once(',').unwrap();
+        assert_eq!(a.parse::<f64>().unwrap(), *x);
+        assert_eq!(b.parse::<f64>().unwrap(), *y);
+    }
+    assert_eq!(text.lines().count(), 4);
+}
+
+#[test]
+fn test_points_from_config() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 2000;
+
+    // The points are exactly those plotted by render_from_config
+    let (xs, ys) = points_from_config(&config).unwrap();
+    assert_eq!(xs.len(), config.npoints);
+    let img = render_from_config(&config).unwrap();
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        assert_ne!(img.get_pixel(*x as u32, *y as u32).0, [0, 0, 0]);
+    }
+
+    // The seed of a report regenerates the same points without rendering
+    let (_, report) = render_reporting(&config).unwrap();
+    assert_eq!(points_from_seed(&config, report.seed), (xs, ys));
+}
+
+#[test]
+fn test_is_degenerate() {
+    // A tiny but nonzero attractor next to a few unconverged points
+    let mut xs = vec![0.0, 0.3, 0.2];
+    let mut ys = vec![0.0, -0.1, 0.4];
+    for i in 0..10000 {
+        xs.push(0.25 + 1e-9 * (i % 7) as f64);
+        ys.push(0.25 + 1e-9 * (i % 5) as f64);
+    }
+    assert!(is_degenerate(&xs, &ys, 1e-2));
+    assert!(!is_degenerate(&xs[3..], &ys[3..], 1e-12));
+    assert!(is_degenerate(&xs[3..], &ys[3..], 1e-6));
+
+    // A random attractor is well spread
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(9));
+    let (xs, ys) = generate_points(&mut seeded_rng(9), &ifs, 10000, 64, 64);
+    assert!(!is_degenerate(&xs, &ys, 1.0));
+
+    assert!(is_degenerate(&[], &[], 1e-3));
+    assert!(is_degenerate(&[f64::NAN, 0.0], &[0.0, 1.0], 1e-3));
+}
+
+#[test]
+fn test_weighted_transform_colors() {
+    // Two overlapping maps, so pixels are visited by both
+    let ifs = SigmaFactorIFS::new(
+        vec![
+            Affine::new(
+                nalgebra::Matrix2::new(0.6, 0.0, 0.0, 0.6),
+                nalgebra::Vector2::new(0.0, 0.0),
+            ),
+            Affine::new(
+                nalgebra::Matrix2::new(0.6, 0.0, 0.0, 0.6),
+                nalgebra::Vector2::new(0.4, 0.4),
+            ),
+        ],
+        vec![1.0, 1.0],
+    );
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 50000;
+    config.color_scheme = ColorScheme::WeightedTransform;
+    config.tone_map = ToneMap::Binary;
+
+    // With binary tone mapping visited pixels get their full color, which
+    // is a mix of Julia colors where the sub-attractors overlap
+    let img = render(seeded_rng(10), &ifs, &config);
+    let julia = [[203, 60, 51], [56, 152, 38], [64, 99, 216], [149, 88, 178]];
+    let mut mixed = 0;
+    for pixel in img.pixels().filter(|p| p.0 != [0, 0, 0]) {
+        if !julia.contains(&pixel.0) {
+            mixed += 1;
+        }
+    }
+    assert!(mixed > 0);
+    assert!(img.pixels().any(|p| julia.contains(&p.0)));
+}
+
+#[test]
+fn test_tile_preview() {
+    let img = image::RgbImage::from_fn(3, 2, |x, y| image::Rgb([x as u8, y as u8, 7]));
+
+    let tiled = tile_preview(&img, 2, 4);
+    assert_eq!(tiled.dimensions(), (12, 4));
+    for (x, y, pixel) in tiled.enumerate_pixels() {
+        assert_eq!(pixel, img.get_pixel(x % 3, y % 2));
+    }
+
+    assert_eq!(tile_preview(&img, 0, 4).dimensions(), (12, 0));
+}
+
+#[test]
+fn test_render_mode() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 2000;
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+
+    // Every mode dispatches to its renderer
+    assert_eq!(config.render_mode, "binary");
+    assert_eq!(
+        render_from_config(&config).unwrap(),
+        render(rng.clone(), &ifs, &config)
+    );
+    config.render_mode = "density".to_string();
+    assert_eq!(
+        render_from_config(&config).unwrap(),
+        render_density(rng.clone(), &ifs, &config)
+    );
+    config.render_mode = "trails".to_string();
+    assert_eq!(
+        render_from_config(&config).unwrap(),
+        render_trails(rng.clone(), &ifs, &config)
+    );
+    config.render_mode = "skeleton".to_string();
+    assert_eq!(
+        render_from_config(&config).unwrap(),
+        render_skeleton(&ifs, &config)
+    );
+
+    // Modes are read from TOML and validated
+    let base = toml::to_string(&Config::new()).unwrap();
+    for mode in RENDER_MODES {
+        let toml = base.replace(
+            "render_mode = \"binary\"",
+            &format!("render_mode = \"{}\"", mode),
+        );
+        assert_eq!(
+            Config::from_reader(toml.as_bytes()).unwrap().render_mode,
+            mode
+        );
+    }
+    config.render_mode = "sketch".to_string();
+    assert!(matches!(config.validate(), Err(Error::ConfigError(_))));
+    assert!(render_from_config(&config).is_err());
+}
+
+#[test]
+fn test_by_weight_desc() {
+    let transforms: Vec<Affine> = (0..4)
+        .map(|i| {
+            Affine::new(
+                nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5),
+                nalgebra::Vector2::new(i as f64, 0.0),
+            )
+        })
+        .collect();
+    let ifs = SigmaFactorIFS::new(transforms, vec![0.1, 0.4, 0.1, 0.4]);
+
+    let sorted = ifs.by_weight_desc();
+    let order: Vec<usize> = sorted.iter().map(|&(i, _, _)| i).collect();
+    assert_eq!(order, vec![1, 3, 0, 2]);
+    for (i, transform, weight) in sorted {
+        assert!(std::ptr::eq(transform, &ifs.transforms()[i]));
+        assert_eq!(weight, ifs.weights()[i]);
+    }
+
+    // The storage order is untouched
+    assert_eq!(ifs.weights(), vec![0.1, 0.4, 0.1, 0.4]);
+}
+
+#[test]
+fn test_composite_over_gradient() {
+    let top = image::Rgb([0, 0, 200]);
+    let bottom = image::Rgb([200, 0, 0]);
+
+    // Fully transparent pixels show the gradient
+    let clear = image::RgbaImage::from_pixel(2, 3, image::Rgba([255, 255, 255, 0]));
+    let img = composite_over_gradient(&clear, top, bottom);
+    assert_eq!(*img.get_pixel(0, 0), top);
+    assert_eq!(*img.get_pixel(1, 1), image::Rgb([100, 0, 100]));
+    assert_eq!(*img.get_pixel(0, 2), bottom);
+
+    // Fully opaque pixels replace it
+    let opaque = image::RgbaImage::from_pixel(2, 3, image::Rgba([10, 20, 30, 255]));
+    let img = composite_over_gradient(&opaque, top, bottom);
+    assert!(img.pixels().all(|p| p.0 == [10, 20, 30]));
+
+    // Half transparent pixels are averaged with the background
+    let half = image::RgbaImage::from_pixel(1, 1, image::Rgba([255, 255, 255, 128]));
+    let img = composite_over_gradient(&half, top, bottom);
+    assert_eq!(img.get_pixel(0, 0).0, [128, 128, 228]);
+}
+
+#[test]
+fn test_symmetry() {
+    let mut config = Config::new();
+    config.height = 64;
+    config.width = 64;
+    config.npoints = 20000;
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(11));
+
+    // A mirrored cloud has a mirror symmetric image
+    config.symmetry = Some(Symmetry::MirrorX);
+    let img = render(seeded_rng(11), &ifs, &config);
+    let mut mirrored = img.clone();
+    flip_horizontal(&mut mirrored);
+    let lit = |img: &image::RgbImage| img.pixels().map(|p| p.0 != [0, 0, 0]).collect::<Vec<_>>();
+    let (a, b) = (lit(&img), lit(&mirrored));
+    let matching = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+    assert!(matching as f64 / a.len() as f64 > 0.95);
+
+    // Rotational symmetry is read from TOML and validated
+    let toml = toml::to_string(&Config::new()).unwrap() + "symmetry = { Rotational = 6 }\n";
+    let parsed = Config::from_reader(toml.as_bytes()).unwrap();
+    assert_eq!(parsed.symmetry, Some(Symmetry::Rotational(6)));
+    config.symmetry = Some(Symmetry::Rotational(0));
+    assert!(config.validate().is_err());
+    config.symmetry = Some(Symmetry::Rotational(MAX_ROTATIONAL_SYMMETRY + 1));
+    assert!(config.validate().is_err());
+    config.symmetry = Some(Symmetry::Rotational(1_000_000));
+    assert!(config.validate().is_err());
+    config.symmetry = Some(Symmetry::Rotational(5));
+    assert!(try_render(seeded_rng(11), &ifs, &config).is_ok());
+}
+
+#[test]
+fn test_best_of() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 2000;
+
+    // A single candidate is the plain render
+    let plain = render_from_config(&config).unwrap();
+    config.best_of = Some(1);
+    assert_eq!(render_from_config(&config).unwrap(), plain);
+
+    // The winner is one of the candidates and is reproducible
+    config.best_of = Some(5);
+    let best = render_from_config(&config).unwrap();
+    assert_eq!(best, render_from_config(&config).unwrap());
+    let candidates: Vec<_> = (0..5)
+        .map(|k| {
+            let mut candidate = config.clone();
+            candidate.best_of = None;
+            candidate.seed += k;
+            render_from_config(&candidate).unwrap()
+        })
+        .collect();
+    assert!(candidates.contains(&best));
+    assert!(coverage(&best, [0, 0, 0]) > 0.0);
+
+    config.best_of = Some(0);
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_render_to_png_bytes() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 48;
+    config.npoints = 1000;
+
+    let bytes = render_to_png_bytes(&config).unwrap();
+    assert!(bytes.starts_with(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']));
+
+    // The bytes decode to the rendered image
+    let decoded = image::load_from_memory(&bytes).unwrap().to_rgb8();
+    assert_eq!(decoded, render_from_config(&config).unwrap());
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn test_render_config_json() {
+    use rust_random_logo::render_config_json;
+
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 1000;
+    let json = serde_json::to_string(&config).unwrap();
+
+    let bytes = render_config_json(&json).unwrap();
+    assert_eq!(bytes, render_to_png_bytes(&config).unwrap());
+
+    assert!(matches!(
+        render_config_json("{ \"height\": "),
+        Err(Error::JsonError(_))
+    ));
+}
+
+#[test]
+fn test_local_contrast() {
+    // A dense block and an isolated pixel of the same gray on black
+    let gray = image::Rgb([100, 100, 100]);
+    let mut img = image::RgbImage::new(32, 32);
+    for y in 4..12 {
+        for x in 4..12 {
+            img.put_pixel(x, y, gray);
+        }
+    }
+    img.put_pixel(24, 24, gray);
+
+    let original = img.clone();
+    local_contrast(&mut img, 1, 0.5);
+
+    // The core of the block brightens and the isolated pixel dims
+    assert_eq!(img.get_pixel(8, 8).0, [150, 150, 150]);
+    assert_eq!(img.get_pixel(24, 24).0, [50, 50, 50]);
+
+    // The background is untouched
+    assert_eq!(img.get_pixel(20, 4).0, [0, 0, 0]);
+
+    // Zero strength is the identity
+    let mut unchanged = original.clone();
+    local_contrast(&mut unchanged, 2, 0.0);
+    assert_eq!(unchanged, original);
+}
+
+#[test]
+fn test_with_active_mask() {
+    let transforms: Vec<Affine> = (0..3)
+        .map(|i| {
+            Affine::new(
+                nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5),
+                nalgebra::Vector2::new(i as f64, 0.0),
+            )
+        })
+        .collect();
+    let ifs = SigmaFactorIFS::new(transforms, vec![1.0, 2.0, 3.0]);
+
+    let masked = ifs.with_active_mask(&[true, false, true]);
+    assert_eq!(masked.transforms().len(), 2);
+    assert_eq!(masked.transforms()[1].b, ifs.transforms()[2].b);
+    assert_eq!(masked.weights(), vec![0.25, 0.75]);
+
+    // Only the kept transformations are ever applied
+    let mut rng = seeded_rng(12);
+    let mut point = nalgebra::Vector2::zeros();
+    for _ in 0..1000 {
+        let (index, next) = masked.apply_random_indexed(&mut rng, &point);
+        assert!(index < 2);
+        point = next;
+    }
+
+    // The original is untouched
+    assert_eq!(ifs.transforms().len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "At least one")]
+fn test_with_active_mask_rejects_empty_mask() {
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(12));
+    let mask = vec![false; ifs.transforms().len()];
+    ifs.with_active_mask(&mask);
+}
+
+#[test]
+fn test_render_rgba() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 20000;
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(13));
+
+    // Opaque points cover exactly the pixels of the binary rendering
+    let binary = render(seeded_rng(13), &ifs, &config);
+    let opaque = render_rgba(seeded_rng(13), &ifs, &config);
+    for (a, b) in binary.pixels().zip(opaque.pixels()) {
+        if a.0 == [0, 0, 0] {
+            assert_eq!(b[3], 0);
+        } else {
+            assert_eq!(b.0, [a[0], a[1], a[2], 255]);
+        }
+    }
+
+    // Translucent points build up opacity with the number of hits
+    config.point_alpha = 0.05;
+    let smoky = render_rgba(seeded_rng(13), &ifs, &config);
+    let alphas: Vec<u8> = smoky.pixels().map(|p| p[3]).filter(|&a| a > 0).collect();
+    assert!(alphas.iter().any(|&a| a < 20));
+    assert!(alphas.iter().any(|&a| a > 200));
+
+    // Out-of-range opacities are clamped
+    config.point_alpha = 3.0;
+    assert_eq!(render_rgba(seeded_rng(13), &ifs, &config), opaque);
+}
+
+#[test]
+fn test_affine_fixed_point() {
+    let affine = Affine::new(
+        nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5),
+        nalgebra::Vector2::new(1.0, 2.0),
+    );
+    let p = affine.fixed_point().unwrap();
+    assert!((p - nalgebra::Vector2::new(2.0, 4.0)).norm() < 1e-12);
+    assert!((affine.apply(&p) - p).norm() < 1e-12);
+
+    // A translation has no fixed point
+    let translation = Affine::new(
+        nalgebra::Matrix2::identity(),
+        nalgebra::Vector2::new(1.0, 0.0),
+    );
+    assert!(translation.fixed_point().is_none());
+}
+
+#[test]
+fn test_render_basins() {
+    // Four maps with fixed points at the corners of the unit square
+    let half = nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.5);
+    let ifs = SigmaFactorIFS::new(
+        vec![
+            Affine::new(half, nalgebra::Vector2::new(0.0, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.5, 0.0)),
+            Affine::new(half, nalgebra::Vector2::new(0.0, 0.5)),
+            Affine::new(half, nalgebra::Vector2::new(0.5, 0.5)),
+        ],
+        vec![1.0; 4],
+    );
+    let mut config = Config::new();
+    config.height = 64;
+    config.width = 64;
+    config.npoints = 50000;
+
+    // The unit square splits into quadrants around the corner fixed points
+    let img = render_basins(&ifs, &config);
+    assert_eq!(img.get_pixel(12, 12).0, [203, 60, 51]);
+    assert_eq!(img.get_pixel(52, 12).0, [56, 152, 38]);
+    assert_eq!(img.get_pixel(12, 52).0, [64, 99, 216]);
+    assert_eq!(img.get_pixel(52, 52).0, [149, 88, 178]);
+
+    // Without fixed points nothing is drawn
+    let drifting = SigmaFactorIFS::new(
+        vec![Affine::new(
+            nalgebra::Matrix2::identity(),
+            nalgebra::Vector2::new(1.0, 0.0),
+        )],
+        vec![1.0],
+    );
+    assert!(render_basins(&drifting, &config)
+        .pixels()
+        .all(|p| p.0 == [0, 0, 0]));
+}
+
+#[test]
+fn test_render_reporting() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 2000;
+
+    let (image, report) = render_reporting(&config).unwrap();
+    assert_eq!(image, render_from_config(&config).unwrap());
+    assert_eq!(report.seed, config.seed);
+    assert_eq!(report.coverage, coverage(&image, config.background));
+
+    // The reported color is the one every point was drawn with
+    let color = report.color.unwrap();
+    assert!(image.pixels().all(|p| p.0 == [0, 0, 0] || p.0 == color));
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    assert_eq!(report.num_transforms, ifs.transforms().len());
+
+    let json = report.to_json().unwrap();
+    assert!(json.contains("\"num_transforms\""));
+
+    // The skeleton mode draws no random color
+    config.render_mode = "skeleton".to_string();
+    assert_eq!(render_reporting(&config).unwrap().1.color, None);
+
+    // Neither do the distance halos nor the orientation scheme
+    config.render_mode = "distance".to_string();
+    assert_eq!(render_reporting(&config).unwrap().1.color, None);
+    config.render_mode = "binary".to_string();
+    config.color_scheme = ColorScheme::Orientation;
+    assert_eq!(render_reporting(&config).unwrap().1.color, None);
+
+    // The color of the density mode is the brightest one of the image
+    config.render_mode = "density".to_string();
+    config.color_scheme = ColorScheme::Single;
+    let (image, report) = render_reporting(&config).unwrap();
+    assert!(image.pixels().any(|p| Some(p.0) == report.color));
+}
+
+#[test]
+fn test_fold_orbit() {
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(14));
+    let (xs, ys) = fold_orbit(
+        &mut seeded_rng(14),
+        &ifs,
+        5000,
+        (Vec::new(), Vec::new()),
+        |(mut xs, mut ys), p| {
+            xs.push(p.x);
+            ys.push(p.y);
+            (xs, ys)
+        },
+    );
+    assert_eq!(xs.len(), 5000);
+
+    // Folding visits the same orbit as the point generators
+    let bounds = fold_orbit(
+        &mut seeded_rng(14),
+        &ifs,
+        5000,
+        (
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ),
+        |(x0, x1, y0, y1), p| (x0.min(p.x), x1.max(p.x), y0.min(p.y), y1.max(p.y)),
+    );
+    assert_eq!(bounds, point_bounds(&xs, &ys));
+    assert_eq!(bounds, attractor_bounds(&mut seeded_rng(14), &ifs, 5000));
+
+    // Custom statistics, like the mean distance to the origin
+    let total = fold_orbit(&mut seeded_rng(14), &ifs, 5000, 0.0, |acc, p| {
+        acc + p.norm()
+    });
+    let expected: f64 = xs.iter().zip(ys.iter()).map(|(x, y)| x.hypot(*y)).sum();
+    assert!((total - expected).abs() < 1e-9 * expected);
+}
+
+#[test]
+fn test_render_splat() {
+    let mut config = Config::new();
+    config.height = 48;
+    config.width = 48;
+    config.npoints = 5000;
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(15));
+    let lit = |img: &image::RgbImage| img.pixels().filter(|p| p.0 != [0, 0, 0]).count();
+
+    // Splats spread around the points and widen with sigma
+    let points = render(seeded_rng(15), &ifs, &config);
+    config.splat_sigma = 0.5;
+    let narrow = render_splat(seeded_rng(15), &ifs, &config);
+    config.splat_sigma = 2.0;
+    let wide = render_splat(seeded_rng(15), &ifs, &config);
+    assert!(lit(&narrow) >= lit(&points));
+    assert!(lit(&wide) > lit(&narrow));
+
+    // The splat mode is available from the configuration
+    config.render_mode = "splat".to_string();
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    assert_eq!(
+        render_from_config(&config).unwrap(),
+        render_splat(rng, &ifs, &config)
+    );
+
+    config.splat_sigma = 0.0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_pretraining_json() {
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(16));
+
+    // Round trip through the reference layout
+    let json = ifs.to_pretraining_json();
+    let parsed = SigmaFactorIFS::from_pretraining_json(&json).unwrap();
+    assert_eq!(parsed.weights(), ifs.weights());
+    for (a, b) in parsed.transforms().iter().zip(ifs.transforms().iter()) {
+        assert_eq!(a.w, b.w);
+        assert_eq!(a.b, b.b);
+    }
+
+    // Matrices are [W | b] rows, and missing probabilities follow the determinants
+    let json =
+        r#"{"system": [[[0.5, 0.0, 1.0], [0.0, 0.25, 2.0]], [[0.5, 0.0, 0.0], [0.0, 0.5, 0.0]]]}"#;
+    let parsed = SigmaFactorIFS::from_pretraining_json(json).unwrap();
+    assert_eq!(
+        parsed.transforms()[0].w,
+        nalgebra::Matrix2::new(0.5, 0.0, 0.0, 0.25)
+    );
+    assert_eq!(parsed.transforms()[0].b, nalgebra::Vector2::new(1.0, 2.0));
+    assert_eq!(parsed.weights(), vec![0.125, 0.25]);
+
+    assert!(matches!(
+        SigmaFactorIFS::from_pretraining_json("{\"system\": 3}"),
+        Err(Error::JsonError(_))
+    ));
+}
+
+#[test]
+fn test_max_memory_bytes() {
+    let mut config = Config::new();
+    assert_eq!(config.max_memory_bytes, DEFAULT_MAX_MEMORY_BYTES);
+    assert!(config.validate().is_ok());
+
+    // An absurd number of points is rejected before allocating anything
+    config.npoints = 1_000_000_000_000;
+    assert!(matches!(config.validate(), Err(Error::ConfigError(_))));
+    assert!(render_from_config(&config).is_err());
+    config.npoints = usize::MAX;
+    assert!(config.validate().is_err());
+
+    // The budget covers the points and the image
+    config.npoints = 1000;
+    config.width = 10;
+    config.height = 10;
+    config.max_memory_bytes = 32 * 1000 + 300;
+    assert_eq!(config.estimated_memory_bytes(), Some(32 * 1000 + 300));
+    assert!(config.validate().is_ok());
+    config.max_memory_bytes -= 1;
+    assert!(config.validate().is_err());
+
+    // Symmetry copies and working buffers count toward the budget
+    config.max_memory_bytes = DEFAULT_MAX_MEMORY_BYTES;
+    config.symmetry = Some(Symmetry::Rotational(4));
+    assert_eq!(config.estimated_memory_bytes(), Some(4 * 32 * 1000 + 300));
+    config.symmetry = None;
+    config.render_mode = "density".to_string();
+    assert_eq!(config.estimated_memory_bytes(), Some(32 * 1000 + 2300));
+    config.render_mode = "binary".to_string();
+    config.color_scheme = ColorScheme::WeightedTransform;
+    config.num_transforms = Some(3);
+    assert_eq!(config.estimated_memory_bytes(), Some(32 * 1000 + 1900));
+    config.color_scheme = ColorScheme::Single;
+    config.post_blur = 1.0;
+    assert_eq!(config.estimated_memory_bytes(), Some(32 * 1000 + 5100));
+
+    config.post_blur = 0.0;
+    config.supersample = 4;
+    assert_eq!(
+        config.estimated_memory_bytes(),
+        Some(32 * 1000 + 100 * (3 + 64))
+    );
+
+    // The supersampling grid of a large canvas exceeds the default budget
+    config.supersample = MAX_SUPERSAMPLE;
+    config.width = 8192;
+    config.height = 8192;
+    assert!(config.validate().is_err());
+    config.supersample = 1;
+    assert!(config.validate().is_ok());
+
+    // A huge canvas with heavy buffers is rejected
+    config.post_blur = 1.0;
+    config.width = 65_536;
+    config.height = 65_536;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_point_cloud_rasterize() {
+    let mut config = Config::new();
+    config.width = 64;
+    config.height = 48;
+    config.npoints = 20_000;
+
+    let cloud = PointCloud::from_config(&config).unwrap();
+    assert_eq!(cloud.len(), config.npoints);
+    assert!(cloud.memory_bytes() >= 16 * cloud.len());
+
+    // The full view draws something, and rasterizing again is deterministic
+    let full = cloud.rasterize(&config, cloud.bounds());
+    assert_eq!(full.dimensions(), (64, 48));
+    assert!(coverage(&full, config.background) > 0.0);
+    assert_eq!(full, cloud.rasterize(&config, cloud.bounds()));
+
+    // A window away from the attractor is blank
+    let (x_min, x_max, y_min, y_max) = cloud.bounds();
+    let far = (x_max + 10.0, x_max + 20.0, y_min, y_max);
+    let blank = cloud.rasterize(&config, far);
+    assert_eq!(coverage(&blank, config.background), 0.0);
+
+    // Zooming into a quarter keeps only the points inside it
+    let quarter = (x_min, (x_min + x_max) / 2.0, y_min, (y_min + y_max) / 2.0);
+    let zoomed = cloud.rasterize(&config, quarter);
+    assert_eq!(zoomed.dimensions(), (64, 48));
+}
+
+#[test]
+#[should_panic(expected = "bounds must satisfy")]
+fn test_point_cloud_rasterize_rejects_empty_window() {
+    let config = Config::new();
+    let cloud = PointCloud {
+        xs: vec![0.0, 1.0],
+        ys: vec![0.0, 1.0],
+        color: image::Rgb([255, 0, 0]),
+    };
+    cloud.rasterize(&config, (1.0, 0.0, 0.0, 1.0));
+}
+
+#[test]
+fn test_translation_range() {
+    // The default range reproduces rand_sigma_factor_ifs
+    let expected = rand_sigma_factor_ifs(&mut seeded_rng(7));
+    let ifs = rand_sigma_factor_ifs_with_translation_range(&mut seeded_rng(7), -1.0, 1.0).unwrap();
+    assert_eq!(ifs.transforms().len(), expected.transforms().len());
+    for (t, e) in ifs.transforms().iter().zip(expected.transforms().iter()) {
+        assert_eq!(t.w, e.w);
+        assert_eq!(t.b, e.b);
+    }
+
+    // Translations stay inside the range
+    let ifs = rand_sigma_factor_ifs_with_translation_range(&mut seeded_rng(7), 2.0, 3.0).unwrap();
+    for t in ifs.transforms() {
+        assert!((2.0..=3.0).contains(&t.b.x));
+        assert!((2.0..=3.0).contains(&t.b.y));
+    }
+
+    // Empty, inverted and non-finite ranges are rejected
+    let mut rng = seeded_rng(7);
+    assert!(matches!(
+        rand_sigma_factor_ifs_with_translation_range(&mut rng, 1.0, 1.0),
+        Err(Error::ConfigError(_))
+    ));
+    assert!(rand_sigma_factor_ifs_with_translation_range(&mut rng, 1.0, -1.0).is_err());
+    assert!(rand_sigma_factor_ifs_with_translation_range(&mut rng, f64::NAN, 1.0).is_err());
+}
+
+#[test]
+fn test_distance_field_single_point() {
+    let background = image::Rgb([0, 0, 0]);
+    let mut img = image::RgbImage::from_pixel(9, 7, background);
+    img.put_pixel(4, 3, image::Rgb([255, 0, 0]));
+
+    let field = distance_field(&img, background);
+    assert_eq!(field.len(), 9 * 7);
+    for y in 0..7 {
+        for x in 0..9 {
+            let expected = ((x as f32 - 4.0).powi(2) + (y as f32 - 3.0).powi(2)).sqrt();
+            assert!((field[y * 9 + x] - expected).abs() < 1e-5);
+        }
+    }
+
+    // Nothing drawn leaves every distance infinite
+    let blank = image::RgbImage::from_pixel(4, 4, background);
+    assert!(distance_field(&blank, background)
+        .iter()
+        .all(|d| d.is_infinite()));
+}
+
+#[test]
+fn test_render_distance_mode() {
+    let mut config = Config::new();
+    config.width = 64;
+    config.height = 64;
+    config.npoints = 5_000;
+    config.render_mode = "distance".to_string();
+    config.halo_radius = 4.0;
+    let image = render_from_config(&config).unwrap();
+    assert!(coverage(&image, config.background) > 0.0);
+
+    // Halos extend the binary rendering
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    let binary_config = Config {
+        render_mode: "binary".to_string(),
+        ..config.clone()
+    };
+    let binary = render(rng.clone(), &ifs, &binary_config);
+    let halos = render_distance(rng.clone(), &ifs, &config);
+    assert!(coverage(&halos, config.background) > coverage(&binary, config.background));
+
+    // render dispatches on the render mode
+    assert_eq!(render(rng, &ifs, &config), halos);
+
+    config.halo_radius = 0.0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_find_seeds() {
+    let mut config = Config::new();
+    config.width = 32;
+    config.height = 32;
+    config.npoints = 2_000;
+
+    // Accepting everything returns the first seeds that render
+    let mut seen: Vec<SeedResult> = Vec::new();
+    let seeds = find_seeds(&config, 10, 3, 100, |result| {
+        seen.push(*result);
+        true
+    })
+    .unwrap();
+    assert_eq!(seeds.len(), 3);
+    assert_eq!(seen.len(), 3);
+    assert!(seeds.windows(2).all(|w| w[0] < w[1]));
+    for result in &seen {
+        assert!(result.coverage > 0.0);
+        assert!((2..=4).contains(&result.num_transforms));
+    }
+
+    // The scan is deterministic and filters by the predicate
+    let predicate = |result: &SeedResult| result.num_transforms == 2;
+    let first = find_seeds(&config, 0, 2, 50, predicate).unwrap();
+    assert_eq!(first, find_seeds(&config, 0, 2, 50, predicate).unwrap());
+    for &seed in &first {
+        let mut rng = seeded_rng(seed);
+        assert_eq!(rand_sigma_factor_ifs(&mut rng).transforms().len(), 2);
+    }
+
+    // max_scan bounds the number of predicate calls
+    let mut calls = 0;
+    let none = find_seeds(&config, 0, 5, 7, |_| {
+        calls += 1;
+        false
+    })
+    .unwrap();
+    assert!(none.is_empty());
+    assert!(calls <= 7);
+
+    config.render_mode = "unknown".to_string();
+    assert!(find_seeds(&config, 0, 1, 1, |_| true).is_err());
+}
+
+#[test]
+fn test_orientation_color_scheme() {
+    // One orientation-preserving transformation and one reflection
+    let ifs = ifs_from_coefficients(
+        &[
+            [0.5, 0.0, 0.0, 0.5, 0.0, 0.0],
+            [-0.5, 0.0, 0.0, 0.5, 1.0, 0.5],
+        ],
+        None,
+    )
+    .unwrap();
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 5000;
+    config.color_scheme = ColorScheme::Orientation;
+
+    let img = render(seeded_rng(3), &ifs, &config);
+    let green = [56, 152, 38];
+    let purple = [149, 88, 178];
+    assert!(img
+        .pixels()
+        .all(|p| p.0 == [0, 0, 0] || p.0 == green || p.0 == purple));
+    assert!(img.pixels().any(|p| p.0 == green));
+    assert!(img.pixels().any(|p| p.0 == purple));
+
+    let config: Config = toml::from_str(
+        "height = 8\nwidth = 8\nnpoints = 10\nifs_name = \"SigmaFactorIFS\"\nndims = 2\n\
+         rng_name = \"Xoshiro256PlusPlus\"\nseed = 1\ncolor_scheme = \"Orientation\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.color_scheme, ColorScheme::Orientation);
+}
+
+#[test]
+fn test_blur() {
+    let mut img = image::RgbImage::from_fn(9, 9, |x, y| {
+        image::Rgb([(x * 20) as u8, (y * 20) as u8, 90])
+    });
+
+    // A zero sigma is the identity
+    let original = img.clone();
+    blur(&mut img, 0.0);
+    assert_eq!(img, original);
+
+    // A uniform image stays uniform, including at the clamped edges
+    let mut flat = image::RgbImage::from_pixel(5, 4, image::Rgb([10, 200, 77]));
+    blur(&mut flat, 2.0);
+    assert!(flat.pixels().all(|p| p.0 == [10, 200, 77]));
+
+    // A single bright pixel spreads symmetrically and dims
+    let mut dot = image::RgbImage::new(9, 9);
+    dot.put_pixel(4, 4, image::Rgb([255, 255, 255]));
+    blur(&mut dot, 1.0);
+    let center = dot.get_pixel(4, 4)[0];
+    assert!(center > 0 && center < 255);
+    assert!(dot.get_pixel(3, 4)[0] > 0);
+    assert_eq!(dot.get_pixel(3, 4), dot.get_pixel(5, 4));
+    assert_eq!(dot.get_pixel(4, 3), dot.get_pixel(4, 5));
+    assert!(dot.get_pixel(3, 4)[0] < center);
+}
+
+#[test]
+fn test_post_blur_config() {
+    let mut config = Config::new();
+    config.width = 32;
+    config.height = 32;
+    config.npoints = 2000;
+    let sharp = render_from_config(&config).unwrap();
+
+    config.post_blur = 1.0;
+    let mut expected = sharp.clone();
+    blur(&mut expected, 1.0);
+    assert_eq!(render_from_config(&config).unwrap(), expected);
+
+    // render reproduces the seed, blur included
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    assert_eq!(render(rng, &ifs, &config), expected);
+
+    config.post_blur = -1.0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_supersample_fixed_point() {
+    let mut config = Config::new();
+    config.width = 48;
+    config.height = 48;
+    config.npoints = 5000;
+    config.supersample = 4;
+
+    let image = render_from_config(&config).unwrap();
+    assert_eq!(image, render_from_config(&config).unwrap());
+
+    // Averaging subpixels produces partially covered pixels
+    let binary = {
+        config.supersample = 1;
+        render_from_config(&config).unwrap()
+    };
+    let colors = |img: &image::RgbImage| {
+        let mut colors: Vec<[u8; 3]> = img.pixels().map(|p| p.0).collect();
+        colors.sort();
+        colors.dedup();
+        colors.len()
+    };
+    assert_eq!(colors(&binary), 2);
+    assert!(colors(&image) > 2);
+
+    // The output is reproduced exactly on every platform; FNV-1a hash of
+    // the pixels of the default seed
+    let hash = image.as_raw().iter().fold(0xcbf29ce484222325u64, |h, &b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    assert_eq!(hash, 15758912968627892493);
+
+    config.supersample = 0;
+    assert!(config.validate().is_err());
+    config.supersample = MAX_SUPERSAMPLE + 1;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_affine_coefficients_round_trip() {
+    let coefficients = [0.5, -0.25, 0.125, 0.75, 1.5, -2.0];
+    let t = Affine::from_coefficients(coefficients);
+    assert_eq!(t.as_coefficients(), coefficients);
+
+    // The map is (a * x + b * y + e, c * x + d * y + f)
+    let p = t.apply(&nalgebra::Vector2::new(2.0, 4.0));
+    assert_eq!(p.x, 0.5 * 2.0 - 0.25 * 4.0 + 1.5);
+    assert_eq!(p.y, 0.125 * 2.0 + 0.75 * 4.0 - 2.0);
+
+    // Random transformations survive the round trip exactly
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(5));
+    for t in ifs.transforms() {
+        let copy = Affine::from_coefficients(t.as_coefficients());
+        assert_eq!(copy.w, t.w);
+        assert_eq!(copy.b, t.b);
+    }
+}
+
+#[cfg(feature = "gif")]
+#[test]
+fn test_render_gif() {
+    use image::AnimationDecoder;
+    use rust_random_logo::render_gif;
+
+    let mut config = Config::new();
+    config.height = 24;
+    config.width = 32;
+    config.npoints = 1000;
+    let a = rand_sigma_factor_ifs_with_count(&mut seeded_rng(1), 3);
+    let b = rand_sigma_factor_ifs_with_count(&mut seeded_rng(2), 3);
+
+    let path = std::env::temp_dir().join(format!("rrl_anim_{}.gif", std::process::id()));
+    render_gif(&[a.clone(), b.clone()], 3, &config, &path).unwrap();
+
+    // Two keyframes loop through two segments
+    let file = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+    let frames = image::codecs::gif::GifDecoder::new(file)
+        .unwrap()
+        .into_frames()
+        .collect_frames()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(frames.len(), 6);
+    assert!(frames.iter().all(|f| f.buffer().dimensions() == (32, 24)));
+
+    // Keyframes must be interpolable
+    let c = rand_sigma_factor_ifs_with_count(&mut seeded_rng(3), 2);
+    assert!(matches!(
+        render_gif(&[a.clone(), c], 3, &config, &path),
+        Err(Error::IfsError(_))
+    ));
+    assert!(render_gif(&[], 3, &config, &path).is_err());
+    assert!(render_gif(&[a], 0, &config, &path).is_err());
+}
+
+#[test]
+fn test_estimate_bounds() {
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(21));
+
+    // Without a margin the estimate is the bounding box of the sample
+    let exact = attractor_bounds(&mut seeded_rng(4), &ifs, 500);
+    assert_eq!(estimate_bounds(&mut seeded_rng(4), &ifs, 500, 0.0), exact);
+
+    // The margin widens every side by a fraction of the extent
+    let (x_min, x_max, y_min, y_max) = estimate_bounds(&mut seeded_rng(4), &ifs, 500, 0.5);
+    let w = exact.1 - exact.0;
+    let h = exact.3 - exact.2;
+    assert!((x_min - (exact.0 - 0.5 * w)).abs() < 1e-12);
+    assert!((x_max - (exact.1 + 0.5 * w)).abs() < 1e-12);
+    assert!((y_min - (exact.2 - 0.5 * h)).abs() < 1e-12);
+    assert!((y_max - (exact.3 + 0.5 * h)).abs() < 1e-12);
+
+    // A generous margin on a small sample covers nearly all of a long orbit
+    let estimate = estimate_bounds(&mut seeded_rng(4), &ifs, 2000, 0.1);
+    let inside = fold_orbit(&mut seeded_rng(4), &ifs, 50_000, 0usize, |count, p| {
+        let contained =
+            (estimate.0..=estimate.1).contains(&p.x) && (estimate.2..=estimate.3).contains(&p.y);
+        count + contained as usize
+    });
+    assert!(inside as f64 / 50_000.0 > 0.99);
+
+    let empty = estimate_bounds(&mut seeded_rng(4), &ifs, 0, 0.0);
+    assert!(empty.0 > empty.1);
+}
+
+#[test]
+#[should_panic(expected = "margin_frac must be non-negative")]
+fn test_estimate_bounds_rejects_negative_margin() {
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(21));
+    estimate_bounds(&mut seeded_rng(4), &ifs, 10, -0.1);
+}
+
+#[test]
+fn test_apply_curves() {
+    let original = image::RgbImage::from_fn(16, 16, |x, y| {
+        image::Rgb([(x * 16) as u8, (y * 16) as u8, (x * y) as u8])
+    });
+
+    // The identity curve leaves the image unchanged
+    let identity = identity_curve();
+    let mut img = original.clone();
+    apply_curves(&mut img, &identity, &identity, &identity);
+    assert_eq!(img, original);
+
+    // Channels are graded independently
+    let invert: [u8; 256] = std::array::from_fn(|v| 255 - v as u8);
+    apply_curves(&mut img, &invert, &identity, &identity);
+    for (graded, source) in img.pixels().zip(original.pixels()) {
+        assert_eq!(graded[0], 255 - source[0]);
+        assert_eq!(graded[1], source[1]);
+        assert_eq!(graded[2], source[2]);
+    }
+
+    // The S-curve keeps the ends and the middle and raises contrast
+    let s = s_curve(1.0);
+    assert_eq!((s[0], s[255]), (0, 255));
+    assert!(s[64] < 64 && s[192] > 192);
+    assert_eq!(s_curve(0.0), identity);
+
+    // Levels clip below black and above white
+    let levels = levels_curve(50, 200, 1.0);
+    assert_eq!(
+        (levels[0], levels[50], levels[125], levels[200], levels[255]),
+        (0, 0, 128, 255, 255)
+    );
+    assert!(levels_curve(0, 255, 2.0)[128] > 128);
+}
+
+#[test]
+#[should_panic(expected = "black must be below white")]
+fn test_levels_curve_rejects_inverted_range() {
+    levels_curve(200, 50, 1.0);
+}
+
+#[cfg(feature = "exr")]
+#[test]
+fn test_save_density_exr() {
+    use rust_random_logo::save_density_exr;
+
+    let mut config = Config::new();
+    config.width = 20;
+    config.height = 10;
+    config.npoints = 2000;
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(8));
+    let counts = density_buffer(&mut seeded_rng(8), &ifs, &config);
+    let buffer: Vec<f32> = counts.iter().map(|&c| c as f32).collect();
+
+    let path = std::env::temp_dir().join(format!("rrl_density_{}.exr", std::process::id()));
+    save_density_exr(&buffer, 20, 10, &path).unwrap();
+
+    // The float values survive the round trip exactly
+    let decoded = exr::prelude::read_first_flat_layer_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let layer = &decoded.layer_data;
+    assert_eq!((layer.size.0, layer.size.1), (20, 10));
+    let channel = &layer.channel_data.list[0];
+    assert_eq!(channel.name.to_string(), "Y");
+    match &channel.sample_data {
+        exr::prelude::FlatSamples::F32(values) => assert_eq!(values, &buffer),
+        _ => panic!("Density must be stored as 32-bit floats"),
+    }
+
+    assert!(matches!(
+        save_density_exr(&buffer, 21, 10, &path),
+        Err(Error::ConfigError(_))
+    ));
+}
+
+#[test]
+fn test_try_render_rejects_empty_orbit() {
+    let ifs = rand_sigma_factor_ifs(&mut seeded_rng(5));
+    let mut config = Config::new();
+    config.height = 16;
+    config.width = 16;
+    config.npoints = 0;
+
+    assert!(matches!(config.validate(), Err(Error::ConfigError(_))));
+    assert!(matches!(
+        try_render(seeded_rng(5), &ifs, &config),
+        Err(Error::ConfigError(_))
+    ));
+}
+
+#[test]
+#[should_panic(expected = "at least two transformations, got 1")]
+fn test_rand_sigma_factor_ifs_with_count_rejects_single_transform() {
+    rand_sigma_factor_ifs_with_count(&mut seeded_rng(3), 1);
+}
+
+#[test]
+#[should_panic(expected = "at least two transformations, got 0")]
+fn test_rand_sigma_factor_ifs_with_count_rejects_zero() {
+    rand_sigma_factor_ifs_with_count(&mut seeded_rng(3), 0);
+}
+
+#[test]
+fn test_render_from_config_accepts_attractor_in_background_color() {
+    let mut config = Config::new();
+    config.height = 32;
+    config.width = 32;
+    config.npoints = 2000;
+    config.max_retries = 0;
+
+    // Points drawn in the background color still count as plotted
+    let (_, report) = render_reporting(&config).unwrap();
+    config.background = report.color.unwrap();
+    let image = render_from_config(&config).unwrap();
+    assert_eq!(coverage(&image, config.background), 0.0);
+    assert_eq!(render_reporting(&config).unwrap().1.seed, config.seed);
+}
+
+#[test]
+fn test_try_render_checks_only_rendering_settings() {
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 2_000,
+        rng_name: "Pcg64".to_string(),
+        ..Config::default()
+    };
+    assert!(config.validate().is_err());
+
+    // The generator and IFS are passed in, so their settings are not checked
+    let mut rng = seeded_rng(config.seed);
+    let ifs = rand_sigma_factor_ifs(&mut rng);
+    assert!(try_render(rng, &ifs, &config).is_ok());
+}
+
+#[test]
+fn test_render_helpers_handle_diverging_orbits() {
+    let config = Config {
+        height: 16,
+        width: 16,
+        npoints: 2_000,
+        transforms: Some(vec![[1e100, 0.0, 0.0, 1e100, 1.0, 1.0]]),
+        ..Config::default()
+    };
+
+    // Degenerate tiles are left blank instead of aborting the grid
+    let grid = render_grid(&config, 1, 2, 0).unwrap();
+    assert!(grid.pixels().all(|p| p.0 == config.background));
+
+    // A frame that cannot be rendered is reported
+    let ifs = ifs_from_coefficients(config.transforms.as_ref().unwrap(), None).unwrap();
+    let frames = render_frames(seeded_rng(0), &ifs, &ifs, 2, &config);
+    assert!(matches!(frames, Err(Error::DegenerateError(_))));
+
+    let mut base = image::RgbImage::new(16, 16);
+    assert!(render_overlay(&mut base, seeded_rng(0), &ifs, &config, (0, 0)).is_err());
+}
+
+#[test]
+fn test_render_density_16_from_seed() {
+    let config = Config {
+        height: 32,
+        width: 32,
+        npoints: 5_000,
+        bit_depth: 16,
+        render_mode: "density".to_string(),
+        ..Config::default()
+    };
+
+    // The 16-bit image of the reported seed agrees with the rendered one
+    let (shallow, report) = render_reporting(&config).unwrap();
+    let deep = render_density_16_from_seed(&config, report.seed);
+    for (a, b) in deep.pixels().zip(shallow.pixels()) {
+        for c in 0..3 {
+            assert!((a[c] as f64 / 257.0 - b[c] as f64).abs() <= 0.5 + 1e-9);
+        }
+    }
+}